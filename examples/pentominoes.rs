@@ -1,104 +1,17 @@
 extern crate pack;
 
-use pack::puzzle::piece::{Position, Template};
-use pack::puzzle::pieces::Bag;
+use pack::catalogue::{one_of_each, pentominoes};
 use pack::puzzle::solver::solve;
+use pack::render::Renderer;
+use pack::render::text::TextRenderer;
 use pack::util::target::rectangle;
 
 fn main() {
     let target = rectangle(3, 20);
-    let bag = pentominoes();
+    let bag = one_of_each(pentominoes());
+    let renderer = TextRenderer::new().with_borders();
 
     solve(&target, bag, &mut |solution|{
-        println!("{}", solution);
-    });
-}
-
-pub fn pentominoes() -> Bag<(i8, i8)> {
-    Bag::new(vec!(
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(2, 0),
-            Position::d2(2, 1),
-            Position::d2(2, 2),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(1, 1),
-            Position::d2(1, 2),
-            Position::d2(2, 2),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(2, 0),
-            Position::d2(3, 0),
-            Position::d2(2, 1),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(1, 1),
-            Position::d2(2, 1),
-            Position::d2(2, 2),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(2, 0),
-            Position::d2(1, 1),
-            Position::d2(1, 2),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(1, 1),
-            Position::d2(1, 2),
-            Position::d2(2, 1),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(2, 0),
-            Position::d2(2, 1),
-            Position::d2(3, 1),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(2, 0),
-            Position::d2(3, 0),
-            Position::d2(3, 1),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(2, 0),
-            Position::d2(3, 0),
-            Position::d2(4, 0),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 1),
-            Position::d2(2, 1),
-            Position::d2(1, 1),
-            Position::d2(2, 1),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 1),
-            Position::d2(1, 0),
-            Position::d2(1, 1),
-            Position::d2(1, 2),
-            Position::d2(2, 1),
-        ))),
-        (1,Template::new(vec!(
-            Position::d2(0, 0),
-            Position::d2(1, 0),
-            Position::d2(2, 0),
-            Position::d2(0, 1),
-            Position::d2(2, 1),
-        ))),
-   ))
+        println!("{}\n", renderer.render(&solution));
+    }).expect("a consistent puzzle");
 }
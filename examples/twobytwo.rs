@@ -21,5 +21,5 @@ fn main() {
     solve(&target, bag, &mut |solution|{
         println!("{}", solution);
         solutions.push(solution);
-    });
+    }).expect("a consistent puzzle");
 }
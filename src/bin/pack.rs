@@ -0,0 +1,287 @@
+//! Command line interface to the packing puzzle solver.
+//!
+//! Puzzles are read from JSON files that have a `target` and a `bag`, in the
+//! shape documented in the crate, and optionally a `name`.
+
+use std::env;
+use std::fmt::Debug;
+use std::fs;
+use std::process;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use pack::puzzle::piece::{MinimumPosition, Normalizable, Piece, Position, Positionable, Transformable, Translatable};
+use pack::puzzle::pieces::{Bag, UNLIMITED};
+use pack::puzzle::solver::{solve_while, Solution, Target};
+use pack::puzzle::verification::verify;
+use pack::puzzle::shape::{Connectivity, Shape};
+use pack::puzzle::{Bounds, Neighbours, VectorAdd, VectorDifference};
+use pack::render::isometric::IsometricRenderer;
+use pack::render::svg::SvgRenderer;
+use pack::render::text::TextRenderer;
+use pack::render::Renderer;
+
+const USAGE: &str = "\
+usage: pack <command> <puzzle.json> [options]
+
+commands:
+  solve                  print all solutions
+  count                  print the number of solutions
+  first                  print the first solution
+  verify <solution.json> check a solution, given in the JSON format of solutions
+  info                   print volumes, orientation counts and placement counts
+
+options:
+  --format text|json|svg output format of solutions, defaults to text;
+                         json prints one solution per line
+  --borders              draw borders between pieces in text output
+  --limit <n>            stop after <n> solutions
+";
+
+fn main() {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    match run(&arguments) {
+        Ok(code) => process::exit(code),
+        Err(message) => {
+            eprintln!("pack: {}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+/// Output format of solutions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Format {
+    Text,
+    Json,
+    Svg,
+}
+
+/// Options that tune the output of commands.
+#[derive(Debug)]
+struct Options {
+    format: Format,
+    borders: bool,
+    limit: Option<usize>,
+}
+
+/// A puzzle as read from a file.
+#[derive(Deserialize)]
+struct Definition<T> {
+    #[serde(default)]
+    name: Option<String>,
+    target: Target<T>,
+    bag: Bag<T>,
+}
+
+fn run(arguments: &[String]) -> Result<i32, String> {
+    let command = arguments.first().ok_or("missing command")?;
+    if command == "help" || command == "--help" {
+        print!("{}", USAGE);
+        return Ok(0);
+    }
+    let path = arguments.get(1).ok_or("missing puzzle file")?;
+
+    let mut rest: Vec<&String> = arguments[2..].iter().collect();
+    let solution_path = if command == "verify" {
+        if rest.is_empty() {
+            return Err(String::from("missing solution file"));
+        }
+        Some(rest.remove(0).clone())
+    } else {
+        None
+    };
+    let options = options(&rest)?;
+
+    let puzzle: Value = read(path)?;
+    let dimension = puzzle["target"][0].as_array().map(|coordinates| coordinates.len()).unwrap_or(2);
+    match dimension {
+        2 => execute::<(i8, i8)>(command, puzzle, solution_path, &options),
+        3 => execute::<(i8, i8, i8)>(command, puzzle, solution_path, &options),
+        n => Err(format!("positions with {} coordinates are not supported", n)),
+    }
+}
+
+fn options(arguments: &[&String]) -> Result<Options, String> {
+    let mut options = Options { format: Format::Text, borders: false, limit: None };
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--format" => {
+                options.format = match arguments.next().map(|value| value.as_str()) {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    Some("svg") => Format::Svg,
+                    Some(other) => return Err(format!("unknown format `{}`", other)),
+                    None => return Err(String::from("missing format")),
+                }
+            },
+            "--borders" => options.borders = true,
+            "--limit" => {
+                let value = arguments.next().ok_or("missing limit")?;
+                options.limit = Some(value.parse().map_err(|_| format!("invalid limit `{}`", value))?);
+            },
+            other => return Err(format!("unknown option `{}`", other)),
+        }
+    }
+    Ok(options)
+}
+
+fn read<D>(path: &str) -> Result<D, String> where D: DeserializeOwned {
+    let contents = fs::read_to_string(path).map_err(|error| format!("could not read `{}`: {}", path, error))?;
+
+    serde_json::from_str(&contents).map_err(|error| format!("could not parse `{}`: {}", path, error))
+}
+
+/// Coordinates of a space in which puzzles can be solved and rendered.
+trait Space: Clone + Debug + Ord + Transformable + Normalizable<Self> + VectorDifference<Self> + VectorAdd<Self> + Neighbours<Self> + Bounds<Self> + Serialize + DeserializeOwned {
+    fn text(renderer: &TextRenderer, solution: &Solution<Self>) -> String;
+
+    fn svg(solutions: &[Solution<Self>]) -> String;
+}
+
+impl Space for (i8, i8) {
+    fn text(renderer: &TextRenderer, solution: &Solution<Self>) -> String {
+        renderer.render(solution)
+    }
+
+    fn svg(solutions: &[Solution<Self>]) -> String {
+        SvgRenderer::new().with_labels().render_sheet(solutions)
+    }
+}
+
+impl Space for (i8, i8, i8) {
+    fn text(renderer: &TextRenderer, solution: &Solution<Self>) -> String {
+        renderer.render(solution)
+    }
+
+    fn svg(solutions: &[Solution<Self>]) -> String {
+        IsometricRenderer::new().render_sheet(solutions)
+    }
+}
+
+fn execute<T>(command: &str, puzzle: Value, solution_path: Option<String>, options: &Options) -> Result<i32, String> where T: Space {
+    let definition: Definition<T> = serde_json::from_value(puzzle).map_err(|error| format!("invalid puzzle: {}", error))?;
+    let definition = Definition { bag: definition.bag.merged(), ..definition };
+
+    match command {
+        "solve" => {
+            print_solutions(&definition, options.limit, options)?;
+            Ok(0)
+        },
+        "first" => {
+            print_solutions(&definition, Some(1), options)?;
+            Ok(0)
+        },
+        "count" => {
+            let mut count = 0;
+            search(&definition, options.limit, &mut |_| count += 1)?;
+            println!("{}", count);
+            Ok(0)
+        },
+        "verify" => {
+            let path = solution_path.unwrap_or_default();
+            let solution: Solution<T> = read(&path)?;
+            match verify(&definition.target, &definition.bag, &solution) {
+                Ok(()) => {
+                    println!("valid");
+                    Ok(0)
+                },
+                Err(reason) => {
+                    println!("invalid: {}", reason);
+                    Ok(1)
+                },
+            }
+        },
+        "info" => {
+            info(&definition)?;
+            Ok(0)
+        },
+        other => Err(format!("unknown command `{}`", other)),
+    }
+}
+
+fn search<T, F>(definition: &Definition<T>, limit: Option<usize>, when_solved: &mut F) -> Result<(), String> where T: Space, F: FnMut(Solution<T>) {
+    let mut found = 0;
+    if limit == Some(0) {
+        return Ok(());
+    }
+    solve_while(&definition.target, definition.bag.clone(), &mut |solution| {
+        when_solved(solution);
+        found += 1;
+        limit.map(|limit| found < limit).unwrap_or(true)
+    }).map_err(|error| format!("invalid puzzle: {}", error))
+}
+
+fn print_solutions<T>(definition: &Definition<T>, limit: Option<usize>, options: &Options) -> Result<(), String> where T: Space {
+    let renderer = if options.borders { TextRenderer::new().with_borders() } else { TextRenderer::new() };
+    let mut solutions = vec!();
+    search(definition, limit, &mut |solution| {
+        match options.format {
+            Format::Text => println!("{}\n", T::text(&renderer, &solution)),
+            Format::Json => println!("{}", serde_json::to_string(&solution).expect("solutions to serialize")),
+            Format::Svg => solutions.push(solution),
+        }
+    })?;
+    if options.format == Format::Svg {
+        print!("{}", T::svg(&solutions));
+    }
+    Ok(())
+}
+
+fn info<T>(definition: &Definition<T>) -> Result<(), String> where T: Space {
+    definition.target.validate().map_err(|error| format!("invalid puzzle: {}", error))?;
+    definition.bag.validate().map_err(|error| format!("invalid puzzle: {}", error))?;
+    let cells: Vec<Position<T>> = definition.target.iter().collect();
+    let pieces = amount(definition.bag.pieces());
+    let volume = amount(definition.bag.volume());
+
+    if let Some(name) = &definition.name {
+        println!("name: {}", name);
+    }
+    println!("target volume: {}", cells.len());
+    if let Some((lower, upper)) = definition.target.bounding_box() {
+        println!("target bounding box: {:?} to {:?}", lower, upper);
+    }
+    println!("target surface area: {}", definition.target.surface_area());
+    println!("target components: {}", definition.target.components(Connectivity::Face).len());
+    println!("target holes: {}", definition.target.holes().len());
+    println!("bag volume: {} ({} templates, {} pieces)", volume, definition.bag.iter().count(), pieces);
+    println!();
+    println!("{:>5}  {:<10} {:>9} {:>6} {:>12} {:>10}", "index", "name", "count", "volume", "orientations", "placements");
+    for (index, (count, template)) in definition.bag.iter().enumerate() {
+        let orientations: Vec<Piece<T>> = template.clone().into_iter().collect();
+        let placements: usize = orientations
+            .iter()
+            .map(|orientation| {
+                let anchor = orientation.minimum_position().unwrap();
+                cells
+                    .iter()
+                    .filter(|cell| {
+                        let mut piece = orientation.clone();
+                        piece.translate(&anchor.to(cell));
+                        definition.target.fits(&piece)
+                    })
+                    .count()
+            })
+            .sum();
+
+        println!(
+            "{:>5}  {:<10} {:>9} {:>6} {:>12} {:>10}",
+            index,
+            template.name().unwrap_or("-"),
+            amount(*count),
+            template.iter().count(),
+            orientations.len(),
+            placements,
+        );
+    }
+    Ok(())
+}
+
+/// Show a count or volume of a `Bag`, which may be unlimited.
+fn amount(value: usize) -> String {
+    if value == UNLIMITED { String::from("unlimited") } else { value.to_string() }
+}
@@ -0,0 +1,68 @@
+//! A catalogue of standard piece sets.
+//!
+//! Every `Template` in the catalogue carries its conventional name, so
+//! solutions made with them render readably. Families of pieces are returned
+//! as a vector of templates; use `one_of_each` to turn them into a `Bag`.
+//!
+//! ```
+//! use pack::catalogue::{one_of_each, pentominoes};
+//!
+//! let bag = one_of_each(pentominoes());
+//! ```
+//!
+//! Classic puzzles, with their published number of solutions, are available
+//! from the registries `planar_puzzles` and `spatial_puzzles`.
+
+mod polycubes;
+mod polyominoes;
+mod puzzles;
+
+pub use self::polycubes::{bedlam, conway, pentacubes, slothouber_graatsma, soma, tetracubes};
+pub use self::polyominoes::{domino, hexominoes, monomino, pentominoes, tetrominoes, trominoes};
+pub use self::puzzles::{planar_puzzles, spatial_puzzles, Puzzle};
+
+use super::puzzle::piece::{Normalizable, Template, Transformable};
+use super::puzzle::pieces::Bag;
+use super::puzzle::VectorAdd;
+
+/// Create a `Bag` that contains every `Template` once, in its canonical form.
+pub fn one_of_each<T>(templates: Vec<Template<T>>) -> Bag<T> where T: Clone + Ord + Transformable + Normalizable<T> + VectorAdd<T> {
+    Bag::normalized(templates.into_iter().map(|template| (1, template)).collect())
+}
+
+/// Find the `Template` called `name` among `templates`.
+pub fn find<T>(templates: Vec<Template<T>>, name: &str) -> Option<Template<T>> {
+    templates.into_iter().find(|template| template.name() == Some(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::hash::Hash;
+    use super::super::puzzle::piece::{Normalizable, Position, Transformable};
+    use super::super::puzzle::VectorAdd;
+    use super::*;
+
+    /// The number of shapes the `templates` take in all their orientations,
+    /// or 0 when two `templates` share a shape.
+    pub(super) fn distinct<T>(templates: Vec<Template<T>>) -> usize where T: Clone + PartialOrd + Ord + Hash + Transformable + Normalizable<T> + VectorAdd<T> {
+        let mut shapes: HashSet<Vec<Position<T>>> = HashSet::new();
+        for template in templates {
+            let orientations: Vec<Vec<Position<T>>> = template.into_iter().map(|piece| piece.iter().collect()).collect();
+            if orientations.iter().all(|orientation| !shapes.contains(orientation)) {
+                shapes.extend(orientations);
+            } else {
+                return 0;
+            }
+        }
+        shapes.len()
+    }
+
+    #[test]
+    fn should_find_templates_by_name() {
+        let template = find(pentominoes(), "X").unwrap();
+
+        assert_eq!(template.name(), Some("X"));
+        assert!(find(pentominoes(), "Q").is_none());
+    }
+}
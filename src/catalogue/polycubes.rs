@@ -0,0 +1,138 @@
+//! Polycubes, spatial pieces made of cubes joined face to face.
+//!
+//! Planar polycubes share their names with the corresponding polyominoes.
+//! The non-planar tetracubes follow the names of the Soma pieces: the screws
+//! `A4` and `B4` and the branch `P4`. Non-planar pentacubes have no generally
+//! agreed upon letters. The ones that equal their mirror image are numbered
+//! `A1` up to `A5`, the others come in mirror image pairs `L1`, `R1` up to
+//! `L6`, `R6`.
+
+use super::super::puzzle::piece::{Position, Template};
+use super::super::puzzle::pieces::Bag;
+use super::find;
+
+fn spatial(name: &str, cells: &[(i8, i8, i8)]) -> Template<(i8, i8, i8)> {
+    Template::new(cells.iter().map(|&(x, y, z)| Position::new(x, y, z)).collect()).with_name(name)
+}
+
+/// A rectangular block of `width` by `height` by `depth` cubes, named after its dimensions.
+fn block(width: i8, height: i8, depth: i8) -> Template<(i8, i8, i8)> {
+    let mut cells = vec!();
+    for x in 0..width {
+        for y in 0..height {
+            for z in 0..depth {
+                cells.push((x, y, z));
+            }
+        }
+    }
+    spatial(&format!("{}x{}x{}", width, height, depth), &cells)
+}
+
+/// The 8 tetracubes, with mirror images counted separately.
+pub fn tetracubes() -> Vec<Template<(i8, i8, i8)>> {
+    vec!(
+        spatial("I4", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (3, 0, 0)]),
+        spatial("L4", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0)]),
+        spatial("N4", &[(0, 0, 0), (1, 0, 0), (1, 1, 0), (2, 1, 0)]),
+        spatial("O4", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0)]),
+        spatial("T4", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (1, 1, 0)]),
+        spatial("A4", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (0, 1, 1)]),
+        spatial("B4", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 0, 1)]),
+        spatial("P4", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (0, 0, 1)]),
+    )
+}
+
+/// The 29 pentacubes, with mirror images counted separately.
+pub fn pentacubes() -> Vec<Template<(i8, i8, i8)>> {
+    vec!(
+        spatial("F", &[(0, 0, 0), (1, 0, 0), (1, 1, 0), (2, 1, 0), (1, 2, 0)]),
+        spatial("I", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (3, 0, 0), (4, 0, 0)]),
+        spatial("L", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (3, 0, 0), (0, 1, 0)]),
+        spatial("N", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (2, 1, 0), (3, 1, 0)]),
+        spatial("P", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0), (1, 1, 0)]),
+        spatial("T", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (1, 1, 0), (1, 2, 0)]),
+        spatial("U", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0), (2, 1, 0)]),
+        spatial("V", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0), (0, 2, 0)]),
+        spatial("W", &[(0, 0, 0), (1, 0, 0), (1, 1, 0), (2, 1, 0), (2, 2, 0)]),
+        spatial("X", &[(1, 0, 0), (0, 1, 0), (1, 1, 0), (2, 1, 0), (1, 2, 0)]),
+        spatial("Y", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (3, 0, 0), (1, 1, 0)]),
+        spatial("Z", &[(0, 0, 0), (1, 0, 0), (1, 1, 0), (1, 2, 0), (2, 2, 0)]),
+        spatial("A1", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0), (0, 0, 1)]),
+        spatial("A2", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (1, 1, 0), (1, 0, 1)]),
+        spatial("A3", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (1, 1, 0), (1, 1, 1)]),
+        spatial("A4", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0), (0, 0, 1)]),
+        spatial("A5", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 0, 1), (0, 1, 1)]),
+        spatial("L1", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (1, 1, 0), (0, 0, 1)]),
+        spatial("R1", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0), (1, 0, 1)]),
+        spatial("L2", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (2, 1, 0), (0, 0, 1)]),
+        spatial("R2", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0), (2, 0, 1)]),
+        spatial("L3", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (2, 1, 0), (2, 1, 1)]),
+        spatial("R3", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0), (0, 1, 1)]),
+        spatial("L4", &[(0, 0, 0), (1, 0, 0), (1, 1, 0), (2, 1, 0), (0, 0, 1)]),
+        spatial("R4", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 0, 1), (2, 0, 1)]),
+        spatial("L5", &[(0, 0, 0), (1, 0, 0), (1, 1, 0), (1, 0, 1), (2, 0, 1)]),
+        spatial("R5", &[(0, 0, 0), (1, 0, 0), (1, 1, 0), (2, 1, 0), (1, 0, 1)]),
+        spatial("L6", &[(0, 0, 0), (1, 0, 0), (1, 0, 1), (1, 1, 1), (2, 1, 1)]),
+        spatial("R6", &[(0, 0, 0), (1, 0, 0), (1, 1, 0), (1, 1, 1), (2, 1, 1)]),
+    )
+}
+
+/// The 7 pieces of the Soma cube, which pack a 3 × 3 × 3 cube.
+pub fn soma() -> Bag<(i8, i8, i8)> {
+    Bag::normalized(vec!(
+        (1, spatial("V", &[(0, 0, 0), (1, 0, 0), (0, 1, 0)])),
+        (1, spatial("L", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (0, 1, 0)])),
+        (1, spatial("T", &[(0, 0, 0), (1, 0, 0), (2, 0, 0), (1, 1, 0)])),
+        (1, spatial("Z", &[(0, 0, 0), (1, 0, 0), (1, 1, 0), (2, 1, 0)])),
+        (1, spatial("A", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (0, 1, 1)])),
+        (1, spatial("B", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 0, 1)])),
+        (1, spatial("P", &[(0, 0, 0), (1, 0, 0), (0, 1, 0), (0, 0, 1)])),
+    ))
+}
+
+/// The 13 pieces of the Bedlam cube, a tetracube and 12 pentacubes that pack a 4 × 4 × 4 cube.
+pub fn bedlam() -> Bag<(i8, i8, i8)> {
+    let mut collection = vec!((1, find(tetracubes(), "B4").unwrap()));
+    for name in ["A1", "A2", "A3", "F", "L1", "L2", "L4", "R3", "R5", "R6", "W", "X"].iter() {
+        collection.push((1, find(pentacubes(), name).unwrap()));
+    }
+    Bag::normalized(collection)
+}
+
+/// The pieces of Conway's puzzle, which pack a 5 × 5 × 5 cube.
+pub fn conway() -> Bag<(i8, i8, i8)> {
+    Bag::normalized(vec!(
+        (13, block(1, 2, 4)),
+        (1, block(2, 2, 2)),
+        (1, block(1, 2, 2)),
+        (3, block(1, 1, 3)),
+    ))
+}
+
+/// The pieces of the Slothouber-Graatsma puzzle, which pack a 3 × 3 × 3 cube.
+pub fn slothouber_graatsma() -> Bag<(i8, i8, i8)> {
+    Bag::normalized(vec!(
+        (6, block(1, 2, 2)),
+        (3, block(1, 1, 1)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::distinct;
+    use super::*;
+
+    #[test]
+    fn families_should_contain_distinct_shapes() {
+        assert_eq!(distinct(tetracubes()), 86);
+        assert_eq!(distinct(pentacubes()), 534);
+    }
+
+    #[test]
+    fn sets_should_fill_their_cubes() {
+        assert_eq!(soma().volume(), 27);
+        assert_eq!(bedlam().volume(), 64);
+        assert_eq!(conway().volume(), 125);
+        assert_eq!(slothouber_graatsma().volume(), 27);
+    }
+}
@@ -0,0 +1,121 @@
+//! Polyominoes, planar pieces made of squares joined edge to edge.
+//!
+//! Trominoes and tetrominoes are named by a letter and their size, e.g. `L4`.
+//! Pentominoes use the letters introduced by Golomb. Hexominoes have no
+//! conventional letters and are numbered `H01` up to `H35`.
+
+use super::super::puzzle::piece::{Position, Template};
+
+fn planar(name: &str, cells: &[(i8, i8)]) -> Template<(i8, i8)> {
+    Template::new(cells.iter().map(|&(x, y)| Position::d2(x, y)).collect()).with_name(name)
+}
+
+/// The single square.
+pub fn monomino() -> Template<(i8, i8)> {
+    planar("I1", &[(0, 0)])
+}
+
+/// Two squares side by side.
+pub fn domino() -> Template<(i8, i8)> {
+    planar("I2", &[(0, 0), (1, 0)])
+}
+
+/// The 2 free trominoes.
+pub fn trominoes() -> Vec<Template<(i8, i8)>> {
+    vec!(
+        planar("I3", &[(0, 0), (1, 0), (2, 0)]),
+        planar("V3", &[(0, 0), (0, 1), (1, 1)]),
+    )
+}
+
+/// The 5 free tetrominoes.
+pub fn tetrominoes() -> Vec<Template<(i8, i8)>> {
+    vec!(
+        planar("I4", &[(0, 0), (1, 0), (2, 0), (3, 0)]),
+        planar("L4", &[(0, 0), (0, 1), (0, 2), (1, 2)]),
+        planar("N4", &[(1, 0), (2, 0), (0, 1), (1, 1)]),
+        planar("O4", &[(0, 0), (1, 0), (0, 1), (1, 1)]),
+        planar("T4", &[(0, 0), (1, 0), (2, 0), (1, 1)]),
+    )
+}
+
+/// The 12 free pentominoes.
+pub fn pentominoes() -> Vec<Template<(i8, i8)>> {
+    vec!(
+        planar("F", &[(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)]),
+        planar("I", &[(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)]),
+        planar("L", &[(0, 0), (0, 1), (0, 2), (0, 3), (1, 3)]),
+        planar("N", &[(1, 0), (1, 1), (0, 2), (1, 2), (0, 3)]),
+        planar("P", &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]),
+        planar("T", &[(0, 0), (1, 0), (2, 0), (1, 1), (1, 2)]),
+        planar("U", &[(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)]),
+        planar("V", &[(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)]),
+        planar("W", &[(0, 0), (0, 1), (1, 1), (1, 2), (2, 2)]),
+        planar("X", &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)]),
+        planar("Y", &[(1, 0), (0, 1), (1, 1), (1, 2), (1, 3)]),
+        planar("Z", &[(0, 0), (1, 0), (1, 1), (1, 2), (2, 2)]),
+    )
+}
+
+/// The 35 free hexominoes.
+pub fn hexominoes() -> Vec<Template<(i8, i8)>> {
+    vec!(
+        planar("H01", &[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0)]),
+        planar("H02", &[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (0, 1)]),
+        planar("H03", &[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (1, 1)]),
+        planar("H04", &[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (2, 1)]),
+        planar("H05", &[(0, 0), (1, 0), (2, 0), (3, 0), (3, 1), (4, 1)]),
+        planar("H06", &[(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (4, 1)]),
+        planar("H07", &[(0, 0), (1, 0), (2, 0), (3, 0), (0, 1), (1, 1)]),
+        planar("H08", &[(0, 0), (1, 0), (2, 0), (3, 0), (0, 1), (2, 1)]),
+        planar("H09", &[(0, 0), (1, 0), (2, 0), (3, 0), (0, 1), (3, 1)]),
+        planar("H10", &[(0, 0), (1, 0), (2, 0), (3, 0), (1, 1), (2, 1)]),
+        planar("H11", &[(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (3, 1)]),
+        planar("H12", &[(0, 0), (1, 0), (2, 0), (1, 1), (2, 1), (3, 1)]),
+        planar("H13", &[(0, 0), (1, 0), (2, 0), (3, 0), (0, 1), (0, 2)]),
+        planar("H14", &[(0, 0), (1, 0), (2, 0), (3, 0), (1, 1), (1, 2)]),
+        planar("H15", &[(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (2, 2)]),
+        planar("H16", &[(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (3, 2)]),
+        planar("H17", &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (3, 2)]),
+        planar("H18", &[(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (1, 2)]),
+        planar("H19", &[(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (2, 2)]),
+        planar("H20", &[(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (3, 2)]),
+        planar("H21", &[(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2)]),
+        planar("H22", &[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (0, 2)]),
+        planar("H23", &[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)]),
+        planar("H24", &[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (2, 2)]),
+        planar("H25", &[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (3, 2)]),
+        planar("H26", &[(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)]),
+        planar("H27", &[(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (2, 2)]),
+        planar("H28", &[(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]),
+        planar("H29", &[(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (0, 2)]),
+        planar("H30", &[(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (1, 2)]),
+        planar("H31", &[(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 2)]),
+        planar("H32", &[(0, 0), (1, 0), (2, 0), (1, 1), (0, 2), (1, 2)]),
+        planar("H33", &[(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2)]),
+        planar("H34", &[(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (2, 2)]),
+        planar("H35", &[(0, 0), (1, 0), (1, 1), (2, 1), (0, 2), (1, 2)]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use super::super::tests::distinct;
+    use super::*;
+
+    #[test]
+    fn families_should_contain_distinct_shapes() {
+        assert_eq!(distinct(trominoes()), 2 + 4);
+        assert_eq!(distinct(tetrominoes()), 2 + 8 + 4 + 1 + 4);
+        assert_eq!(distinct(pentominoes()), 63);
+        assert_eq!(distinct(hexominoes()), 216);
+    }
+
+    #[test]
+    fn names_should_be_unique() {
+        let names: HashSet<String> = hexominoes().iter().map(|template| template.name().unwrap().to_string()).collect();
+
+        assert_eq!(names.len(), 35);
+    }
+}
@@ -0,0 +1,103 @@
+//! Classic puzzles with their published number of solutions.
+
+use super::super::puzzle::pieces::Bag;
+use super::super::puzzle::solver::Target;
+use super::super::util::target::{brick, rectangle};
+use super::{bedlam, one_of_each, pentominoes, slothouber_graatsma, soma};
+
+/// A packing problem together with its known number of solutions.
+///
+/// Published counts usually consider solutions that differ by a symmetry of
+/// the target the same. The solver reports every solution, so where it is
+/// known the total number of solutions is recorded as well.
+#[derive(Debug)]
+pub struct Puzzle<T> {
+    name: String,
+    target: Target<T>,
+    bag: Bag<T>,
+    solutions: u64,
+    total_solutions: Option<u64>,
+}
+
+impl<T> Puzzle<T> {
+    /// Create a `Puzzle` that has `solutions` solutions up to symmetry.
+    pub fn new<S>(name: S, target: Target<T>, bag: Bag<T>, solutions: u64) -> Puzzle<T> where S: Into<String> {
+        Puzzle { name: name.into(), target, bag, solutions, total_solutions: None }
+    }
+
+    /// Create a `Puzzle` from this one, that has `total_solutions` solutions
+    /// when symmetric solutions are counted separately.
+    pub fn with_total_solutions(self, total_solutions: u64) -> Puzzle<T> {
+        Puzzle { total_solutions: Some(total_solutions), ..self }
+    }
+
+    /// The name of this `Puzzle`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The region to pack.
+    pub fn target(&self) -> &Target<T> {
+        &self.target
+    }
+
+    /// The pieces to pack.
+    pub fn bag(&self) -> &Bag<T> {
+        &self.bag
+    }
+
+    /// The published number of solutions, up to symmetry of the target.
+    pub fn solutions(&self) -> u64 {
+        self.solutions
+    }
+
+    /// The number of solutions the solver should find, if known.
+    pub fn total_solutions(&self) -> Option<u64> {
+        self.total_solutions
+    }
+}
+
+/// The registry of planar puzzles.
+pub fn planar_puzzles() -> Vec<Puzzle<(i8, i8)>> {
+    vec!(
+        Puzzle::new("pentominoes 3x20", rectangle(3, 20), one_of_each(pentominoes()), 2).with_total_solutions(8),
+        Puzzle::new("pentominoes 4x15", rectangle(4, 15), one_of_each(pentominoes()), 368).with_total_solutions(1472),
+        Puzzle::new("pentominoes 5x12", rectangle(5, 12), one_of_each(pentominoes()), 1010).with_total_solutions(4040),
+        Puzzle::new("pentominoes 6x10", rectangle(6, 10), one_of_each(pentominoes()), 2339).with_total_solutions(9356),
+    )
+}
+
+/// The registry of spatial puzzles.
+pub fn spatial_puzzles() -> Vec<Puzzle<(i8, i8, i8)>> {
+    vec!(
+        Puzzle::new("Slothouber-Graatsma", brick(3, 3, 3), slothouber_graatsma(), 1).with_total_solutions(8),
+        Puzzle::new("Soma cube", brick(3, 3, 3), soma(), 240).with_total_solutions(11520),
+        Puzzle::new("Bedlam cube", brick(4, 4, 4), bedlam(), 19186),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::solver::solve;
+    use super::*;
+
+    #[test]
+    fn registry_should_balance_volumes() {
+        for puzzle in planar_puzzles() {
+            assert_eq!(puzzle.target().iter().count(), puzzle.bag().volume(), "{}", puzzle.name());
+        }
+        for puzzle in spatial_puzzles() {
+            assert_eq!(puzzle.target().iter().count(), puzzle.bag().volume(), "{}", puzzle.name());
+        }
+    }
+
+    #[test]
+    fn solver_should_find_all_slothouber_graatsma_solutions() {
+        let puzzle = spatial_puzzles().into_iter().find(|puzzle| puzzle.name() == "Slothouber-Graatsma").unwrap();
+        let mut count = 0;
+
+        solve(puzzle.target(), puzzle.bag().clone(), &mut |_| count += 1).unwrap();
+
+        assert_eq!(Some(count), puzzle.total_solutions());
+    }
+}
@@ -0,0 +1,115 @@
+//! Surfaces of collections of voxels.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Rectangular face of a surface, on a grid of integer coordinates.
+///
+/// The corners are ordered counter clockwise when seen from outside.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct Quad {
+    pub corners: [(i32, i32, i32); 4],
+}
+
+impl Quad {
+    /// Outward normal of this `Quad`, a unit vector along one of the axes.
+    pub fn normal(&self) -> (i32, i32, i32) {
+        let [a, b, c, _] = self.corners;
+        let u = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+        let v = (c.0 - b.0, c.1 - b.1, c.2 - b.2);
+        let n = (u.1 * v.2 - u.2 * v.1, u.2 * v.0 - u.0 * v.2, u.0 * v.1 - u.1 * v.0);
+
+        (n.0.signum(), n.1.signum(), n.2.signum())
+    }
+}
+
+/// Determine the faces of the voxels that are not shared with an other voxel.
+///
+/// The voxel `(x, y, z)` is the cube between the grid points `(x, y, z)` and
+/// `(x + 1, y + 1, z + 1)`. When `merge` is set, coplanar adjacent faces are
+/// merged into larger rectangles.
+pub(crate) fn surface(voxels: &HashSet<(i32, i32, i32)>, merge: bool) -> Vec<Quad> {
+    let mut planes: BTreeMap<(usize, bool, i32), BTreeSet<(i32, i32)>> = BTreeMap::new();
+    for &voxel in voxels {
+        let coordinates = [voxel.0, voxel.1, voxel.2];
+        for axis in 0..3 {
+            for &positive in [false, true].iter() {
+                let mut neighbour = coordinates;
+                neighbour[axis] += if positive { 1 } else { -1 };
+                if !voxels.contains(&(neighbour[0], neighbour[1], neighbour[2])) {
+                    let layer = coordinates[axis] + if positive { 1 } else { 0 };
+                    let cell = (coordinates[(axis + 1) % 3], coordinates[(axis + 2) % 3]);
+                    planes.entry((axis, positive, layer)).or_default().insert(cell);
+                }
+            }
+        }
+    }
+
+    let mut quads = vec!();
+    for ((axis, positive, layer), cells) in planes {
+        for (start, end) in rectangles(cells, merge) {
+            let point = |u: i32, v: i32| {
+                let mut point = [0; 3];
+                point[axis] = layer;
+                point[(axis + 1) % 3] = u;
+                point[(axis + 2) % 3] = v;
+                (point[0], point[1], point[2])
+            };
+            let mut corners = [point(start.0, start.1), point(end.0, start.1), point(end.0, end.1), point(start.0, end.1)];
+            if !positive {
+                corners.reverse();
+            }
+            quads.push(Quad { corners });
+        }
+    }
+    quads
+}
+
+/// Cover the cells with rectangles, each given by its lower and upper corner.
+fn rectangles(mut cells: BTreeSet<(i32, i32)>, merge: bool) -> Vec<((i32, i32), (i32, i32))> {
+    let mut result = vec!();
+    while let Some(&(u, v)) = cells.iter().next() {
+        let mut height = 1;
+        let mut width = 1;
+        if merge {
+            while cells.contains(&(u, v + height)) {
+                height += 1;
+            }
+            while (0..height).all(|dv| cells.contains(&(u + width, v + dv))) {
+                width += 1;
+            }
+        }
+        for du in 0..width {
+            for dv in 0..height {
+                cells.remove(&(u + du, v + dv));
+            }
+        }
+        result.push(((u, v), (u + width, v + height)));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cube_should_have_six_outward_faces() {
+        let voxels: HashSet<(i32, i32, i32)> = vec!((0, 0, 0)).into_iter().collect();
+
+        let quads = surface(&voxels, true);
+
+        let normals: HashSet<(i32, i32, i32)> = quads.iter().map(Quad::normal).collect();
+        assert_eq!(quads.len(), 6);
+        assert_eq!(normals.len(), 6);
+        assert!(quads.iter().filter(|quad| quad.normal() == (1, 0, 0)).all(|quad| quad.corners.iter().all(|c| c.0 == 1)));
+        assert!(quads.iter().filter(|quad| quad.normal() == (-1, 0, 0)).all(|quad| quad.corners.iter().all(|c| c.0 == 0)));
+    }
+
+    #[test]
+    fn coplanar_faces_should_merge() {
+        let voxels: HashSet<(i32, i32, i32)> = vec!((0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0)).into_iter().collect();
+
+        assert_eq!(surface(&voxels, true).len(), 6);
+        assert_eq!(surface(&voxels, false).len(), 16);
+    }
+}
@@ -0,0 +1,5 @@
+//! Exporting packing problems to formats of other tools.
+
+mod mesh;
+pub mod obj;
+pub mod print;
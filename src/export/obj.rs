@@ -0,0 +1,173 @@
+//! Exporting spatial solutions as Wavefront OBJ meshes.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use super::mesh::surface;
+use super::super::puzzle::solver::Solution;
+use super::super::render::{labels, Colour};
+
+/// Contents of a Wavefront OBJ file and its accompanying MTL file.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Wavefront {
+    /// Geometry, one object per piece.
+    pub obj: String,
+    /// Materials, one per piece.
+    pub mtl: String,
+}
+
+/// Exports a spatial `Solution` as a Wavefront mesh.
+///
+/// Every piece becomes an object that consists of the exterior faces of its
+/// cubes, with coplanar faces merged into larger quads, and its own material.
+#[derive(Debug, Clone)]
+pub struct ObjExporter {
+    cell_size: f64,
+    material_library: String,
+}
+
+impl ObjExporter {
+    /// Create an `ObjExporter` with cubes of unit size, that refers to the
+    /// material library `solution.mtl`.
+    pub fn new() -> ObjExporter {
+        ObjExporter { cell_size: 1.0, material_library: String::from("solution.mtl") }
+    }
+
+    /// Create an `ObjExporter` from this one with cubes of `cell_size`.
+    pub fn with_cell_size(self, cell_size: f64) -> ObjExporter {
+        ObjExporter { cell_size, ..self }
+    }
+
+    /// Create an `ObjExporter` from this one that refers to the material library `name`.
+    pub fn with_material_library<S>(self, name: S) -> ObjExporter where S: Into<String> {
+        ObjExporter { material_library: name.into(), ..self }
+    }
+
+    /// Export the `Solution`.
+    pub fn export(&self, solution: &Solution<(i8, i8, i8)>) -> Wavefront {
+        let pieces = solution.pieces();
+        let labels: Vec<String> = labels(&pieces).iter().map(|label| identifier(label)).collect();
+
+        let mut obj = String::new();
+        writeln!(obj, "mtllib {}", self.material_library).unwrap();
+        let mut offset = 1;
+        let mut normal_offset = 1;
+        for (piece, label) in pieces.iter().zip(labels.iter()) {
+            let voxels: HashSet<(i32, i32, i32)> = piece
+                .iter()
+                .map(|position| {
+                    let (x, y, z) = *position.coordinates();
+                    (x as i32, y as i32, z as i32)
+                })
+                .collect();
+            let quads = surface(&voxels, true);
+
+            let mut vertices: HashMap<(i32, i32, i32), usize> = HashMap::new();
+            let mut order: Vec<(i32, i32, i32)> = vec!();
+            let mut normals: Vec<(i32, i32, i32)> = vec!();
+            let faces: Vec<(Vec<usize>, usize)> = quads
+                .iter()
+                .map(|quad| {
+                    let corners = quad.corners
+                        .iter()
+                        .map(|&corner| {
+                            *vertices.entry(corner).or_insert_with(|| {
+                                order.push(corner);
+                                offset + order.len() - 1
+                            })
+                        })
+                        .collect();
+                    let normal = quad.normal();
+                    let index = normals.iter().position(|&n| n == normal).unwrap_or_else(|| {
+                        normals.push(normal);
+                        normals.len() - 1
+                    });
+                    (corners, normal_offset + index)
+                })
+                .collect();
+
+            writeln!(obj, "o {}", label).unwrap();
+            for (x, y, z) in &order {
+                writeln!(obj, "v {} {} {}", *x as f64 * self.cell_size, *y as f64 * self.cell_size, *z as f64 * self.cell_size).unwrap();
+            }
+            for (x, y, z) in &normals {
+                writeln!(obj, "vn {} {} {}", x, y, z).unwrap();
+            }
+            writeln!(obj, "usemtl {}", label).unwrap();
+            for (corners, normal) in faces {
+                let indices: Vec<String> = corners.iter().map(|index| format!("{}//{}", index, normal)).collect();
+                writeln!(obj, "f {}", indices.join(" ")).unwrap();
+            }
+            normal_offset += normals.len();
+            offset += order.len();
+        }
+
+        let mut mtl = String::new();
+        for (index, label) in labels.iter().enumerate() {
+            let colour = Colour::distinct(index);
+            writeln!(mtl, "newmtl {}", label).unwrap();
+            writeln!(
+                mtl,
+                "Kd {:.3} {:.3} {:.3}",
+                colour.0 as f64 / 255.0,
+                colour.1 as f64 / 255.0,
+                colour.2 as f64 / 255.0,
+            ).unwrap();
+        }
+
+        Wavefront { obj, mtl }
+    }
+}
+
+impl Default for ObjExporter {
+    fn default() -> Self {
+        ObjExporter::new()
+    }
+}
+
+/// Replace whitespace so `label` can be used as a name in OBJ and MTL files.
+fn identifier(label: &str) -> String {
+    label.split_whitespace().collect::<Vec<&str>>().join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::piece::{Piece, Position};
+    use super::*;
+
+    #[test]
+    fn should_export_an_object_per_piece() {
+        let solution = Solution::empty()
+            .record(&Piece::named(vec!(Position::new(0, 0, 0), Position::new(1, 0, 0)), "I 2"))
+            .record(&Piece::new(vec!(Position::new(0, 1, 0))));
+
+        let wavefront = ObjExporter::new().export(&solution);
+
+        assert!(wavefront.obj.starts_with("mtllib solution.mtl\no I_2\n"));
+        assert!(wavefront.obj.contains("\no A\n"));
+        assert!(wavefront.obj.contains("\nusemtl I_2\n"));
+        assert_eq!(wavefront.obj.matches("\nf ").count(), 12);
+        assert_eq!(wavefront.obj.matches("\nv ").count(), 16);
+        assert_eq!(wavefront.obj.matches("\nvn ").count(), 12);
+        assert!(wavefront.obj.contains("\nv 2 1 1\n"));
+        assert_eq!(wavefront.mtl, "newmtl I_2\nKd 0.843 0.259 0.259\nnewmtl A\nKd 0.259 0.843 0.427\n");
+    }
+
+    #[test]
+    fn faces_should_index_vertices_of_their_own_object() {
+        let solution = Solution::empty()
+            .record(&Piece::new(vec!(Position::new(0, 0, 0))))
+            .record(&Piece::new(vec!(Position::new(5, 0, 0))));
+
+        let wavefront = ObjExporter::new().with_cell_size(2.0).export(&solution);
+
+        assert!(wavefront.obj.contains("\nv 12 2 2\n"));
+        let second = wavefront.obj.split("o B").nth(1).unwrap();
+        assert!(second.lines().filter(|line| line.starts_with("f ")).all(|line| {
+            line[2..].split(' ').all(|corner| {
+                let indices: Vec<usize> = corner.split("//").map(|index| index.parse().unwrap()).collect();
+                indices[0] > 8 && indices[1] > 6
+            })
+        }));
+    }
+}
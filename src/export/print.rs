@@ -0,0 +1,368 @@
+//! Exporting pieces as printable solids.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::mesh::{surface, Quad};
+use super::super::puzzle::piece::{CubeSymmetry, CubeSymmetryIterator, Piece, Template, Transformable};
+
+/// A point in space, in the units of the cell size.
+type Point = (f64, f64, f64);
+
+/// Exports a spatial `Template` as a solid for 3D printing.
+///
+/// The outer faces of the solid are moved inwards by half the clearance, so
+/// printed pieces fit together. Faces between cubes of the same piece are not
+/// moved, which keeps the piece in one part. The piece rests on the plane
+/// `z = 0` in the chosen orientation.
+#[derive(Debug, Clone)]
+pub struct PrintExporter {
+    cell_size: f64,
+    clearance: f64,
+    fillet: f64,
+    orientation: CubeSymmetry,
+}
+
+impl PrintExporter {
+    /// Create a `PrintExporter` with cubes of 10 units, without clearance or
+    /// fillets, that keeps the orientation of the `Template`.
+    pub fn new() -> PrintExporter {
+        PrintExporter { cell_size: 10.0, clearance: 0.0, fillet: 0.0, orientation: CubeSymmetry::E0123 }
+    }
+
+    /// Create a `PrintExporter` from this one with cubes of `cell_size`.
+    pub fn with_cell_size(self, cell_size: f64) -> PrintExporter {
+        PrintExporter { cell_size, ..self }
+    }
+
+    /// Create a `PrintExporter` from this one that leaves a gap of `clearance`
+    /// between the outer faces of adjacent pieces. The clearance should be
+    /// smaller than the cell size.
+    pub fn with_clearance(self, clearance: f64) -> PrintExporter {
+        PrintExporter { clearance, ..self }
+    }
+
+    /// Create a `PrintExporter` from this one that rounds the inner edges
+    /// between cubes with fillets of `radius`. Only OpenSCAD output has fillets.
+    pub fn with_fillet(self, radius: f64) -> PrintExporter {
+        PrintExporter { fillet: radius, ..self }
+    }
+
+    /// Create a `PrintExporter` from this one that prints pieces transformed by `orientation`.
+    pub fn with_orientation(self, orientation: CubeSymmetry) -> PrintExporter {
+        PrintExporter { orientation, ..self }
+    }
+
+    /// Export the `Template` as OpenSCAD source.
+    pub fn openscad(&self, template: &Template<(i8, i8, i8)>) -> String {
+        let cells = self.cells(template);
+        let (points, faces) = self.polyhedron(&cells);
+
+        let mut scad = String::new();
+        writeln!(scad, "// {}", template.name().unwrap_or("piece")).unwrap();
+        let fillets = self.fillets(&cells);
+        let filleted = !fillets.is_empty();
+        if filleted {
+            scad.push_str("union() {\n");
+        }
+        let points: Vec<String> = points.iter().map(|p| format!("[{}, {}, {}]", p.0, p.1, p.2)).collect();
+        let faces: Vec<String> = faces
+            .iter()
+            .map(|face| {
+                let indices: Vec<String> = face.iter().map(|index| index.to_string()).collect();
+                format!("[{}]", indices.join(", "))
+            })
+            .collect();
+        writeln!(scad, "polyhedron(\n  points = [{}],\n  faces = [{}]\n);", points.join(", "), faces.join(", ")).unwrap();
+        for fillet in fillets {
+            scad.push_str(&fillet);
+        }
+        if filleted {
+            scad.push_str("}\n");
+        }
+        scad
+    }
+
+    /// Export the `Template` as an ASCII STL file.
+    pub fn stl(&self, template: &Template<(i8, i8, i8)>) -> String {
+        let cells = self.cells(template);
+        let name = template.name().unwrap_or("piece").split_whitespace().collect::<Vec<&str>>().join("_");
+
+        let mut stl = String::new();
+        writeln!(stl, "solid {}", name).unwrap();
+        for quad in self.quads(&cells) {
+            let normal = quad.normal();
+            let [a, b, c, d] = quad.corners;
+            for triangle in [[a, b, c], [a, c, d]].iter() {
+                writeln!(stl, "  facet normal {} {} {}", normal.0, normal.1, normal.2).unwrap();
+                stl.push_str("    outer loop\n");
+                for &corner in triangle.iter() {
+                    let (x, y, z) = self.point(corner);
+                    writeln!(stl, "      vertex {} {} {}", x, y, z).unwrap();
+                }
+                stl.push_str("    endloop\n  endfacet\n");
+            }
+        }
+        writeln!(stl, "endsolid {}", name).unwrap();
+        stl
+    }
+
+    /// The cells of the `Template` in print orientation, with the smallest
+    /// coordinates on every axis equal to zero.
+    fn cells(&self, template: &Template<(i8, i8, i8)>) -> HashSet<(i32, i32, i32)> {
+        let mut piece = Piece::new(template.iter().collect());
+        piece.transform(&self.orientation);
+
+        let cells: Vec<(i32, i32, i32)> = piece
+            .iter()
+            .map(|position| {
+                let (x, y, z) = *position.coordinates();
+                (x as i32, y as i32, z as i32)
+            })
+            .collect();
+        let min_x = cells.iter().map(|c| c.0).min().unwrap_or(0);
+        let min_y = cells.iter().map(|c| c.1).min().unwrap_or(0);
+        let min_z = cells.iter().map(|c| c.2).min().unwrap_or(0);
+
+        cells.iter().map(|c| (c.0 - min_x, c.1 - min_y, c.2 - min_z)).collect()
+    }
+
+    fn eroded(&self) -> bool {
+        self.clearance > 0.0
+    }
+
+    /// Faces of the solid on a grid of integer coordinates.
+    ///
+    /// With clearance every cell is split in three along each axis: a slab of
+    /// half the clearance on both sides and the middle. A part of a cell is
+    /// solid when all cells it borders belong to the piece.
+    fn quads(&self, cells: &HashSet<(i32, i32, i32)>) -> Vec<Quad> {
+        if !self.eroded() {
+            return surface(cells, false);
+        }
+
+        let mut voxels = HashSet::new();
+        for &(x, y, z) in cells {
+            for part in 0..27 {
+                let parts = (part % 3, (part / 3) % 3, part / 9);
+                let offset = (parts.0 - 1, parts.1 - 1, parts.2 - 1);
+                let solid = (0..8).all(|mask| {
+                    let dx = if mask & 1 == 1 { offset.0 } else { 0 };
+                    let dy = if mask & 2 == 2 { offset.1 } else { 0 };
+                    let dz = if mask & 4 == 4 { offset.2 } else { 0 };
+                    cells.contains(&(x + dx, y + dy, z + dz))
+                });
+                if solid {
+                    voxels.insert((3 * x + parts.0, 3 * y + parts.1, 3 * z + parts.2));
+                }
+            }
+        }
+        surface(&voxels, false)
+    }
+
+    /// Points and faces of an OpenSCAD polyhedron. OpenSCAD expects the points
+    /// of a face in clockwise order when seen from outside.
+    fn polyhedron(&self, cells: &HashSet<(i32, i32, i32)>) -> (Vec<Point>, Vec<Vec<usize>>) {
+        let mut corners: Vec<(i32, i32, i32)> = vec!();
+        let faces = self
+            .quads(cells)
+            .iter()
+            .map(|quad| {
+                quad.corners
+                    .iter()
+                    .rev()
+                    .map(|corner| {
+                        corners.iter().position(|c| c == corner).unwrap_or_else(|| {
+                            corners.push(*corner);
+                            corners.len() - 1
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        (corners.into_iter().map(|corner| self.point(corner)).collect(), faces)
+    }
+
+    /// OpenSCAD fillets for the concave edges between cubes of the piece.
+    fn fillets(&self, cells: &HashSet<(i32, i32, i32)>) -> Vec<String> {
+        if self.fillet <= 0.0 {
+            return vec!();
+        }
+
+        let size = self.cell_size;
+        let half = self.clearance / 2.0;
+        let radius = self.fillet;
+        let unit = |axis: usize, sign: i32| {
+            let mut v = [0; 3];
+            v[axis] = sign;
+            v
+        };
+        let add = |a: [i32; 3], b: [i32; 3]| [a[0] + b[0], a[1] + b[1], a[2] + b[2]];
+        let contains = |c: [i32; 3]| cells.contains(&(c[0], c[1], c[2]));
+        let concave = |a: [i32; 3], p: [i32; 3], q: [i32; 3]| {
+            contains(a) && contains(add(a, p)) && contains(add(a, q)) && !contains(add(add(a, p), q))
+        };
+
+        let mut sorted: Vec<&(i32, i32, i32)> = cells.iter().collect();
+        sorted.sort();
+        let mut result = vec!();
+        for &&(x, y, z) in &sorted {
+            let a = [x, y, z];
+            for p in 0..3 {
+                for q in (p + 1)..3 {
+                    let r = 3 - p - q;
+                    for &(sp, sq) in [(1, 1), (1, -1), (-1, 1), (-1, -1)].iter() {
+                        let (ep, eq) = (unit(p, sp), unit(q, sq));
+                        if !concave(a, ep, eq) {
+                            continue;
+                        }
+                        let below = concave(add(a, unit(r, -1)), ep, eq);
+                        let above = concave(add(a, unit(r, 1)), ep, eq);
+                        let start = a[r] as f64 * size + if below { 0.0 } else { half };
+                        let end = (a[r] + 1) as f64 * size - if above { 0.0 } else { half };
+
+                        let mut origin = [0.0; 3];
+                        origin[p] = (a[p] + if sp > 0 { 1 } else { 0 }) as f64 * size - sp as f64 * half;
+                        origin[q] = (a[q] + if sq > 0 { 1 } else { 0 }) as f64 * size - sq as f64 * half;
+                        origin[r] = start;
+
+                        let mut matrix = [[0.0; 4]; 3];
+                        matrix[p][0] = sp as f64;
+                        matrix[q][1] = sq as f64;
+                        matrix[r][2] = 1.0;
+                        for row in 0..3 {
+                            matrix[row][3] = origin[row];
+                        }
+                        let rows: Vec<String> = matrix
+                            .iter()
+                            .map(|row| format!("[{}, {}, {}, {}]", row[0], row[1], row[2], row[3]))
+                            .collect();
+
+                        result.push(format!(
+                            "multmatrix([{}, [0, 0, 0, 1]]) difference() {{ cube([{r}, {r}, {h}]); translate([{r}, {r}, -1]) cylinder(r = {r}, h = {l}, $fn = 24); }}\n",
+                            rows.join(", "),
+                            r = radius,
+                            h = end - start,
+                            l = end - start + 2.0,
+                        ));
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn coordinate(&self, line: i32) -> f64 {
+        if !self.eroded() {
+            return line as f64 * self.cell_size;
+        }
+
+        let cell = line.div_euclid(3) as f64;
+        match line.rem_euclid(3) {
+            0 => cell * self.cell_size,
+            1 => cell * self.cell_size + self.clearance / 2.0,
+            _ => (cell + 1.0) * self.cell_size - self.clearance / 2.0,
+        }
+    }
+
+    fn point(&self, (x, y, z): (i32, i32, i32)) -> Point {
+        (self.coordinate(x), self.coordinate(y), self.coordinate(z))
+    }
+}
+
+impl Default for PrintExporter {
+    fn default() -> Self {
+        PrintExporter::new()
+    }
+}
+
+/// Determine the orientation in which the `Template` has the most cubes on
+/// the print bed, preferring lower pieces when there is a tie.
+pub fn flattest_orientation(template: &Template<(i8, i8, i8)>) -> CubeSymmetry {
+    CubeSymmetryIterator::new()
+        .max_by_key(|symmetry| {
+            let mut piece = Piece::new(template.iter().collect());
+            piece.transform(symmetry);
+            let heights: Vec<i8> = piece.iter().map(|position| position.coordinates().2).collect();
+            let bottom = heights.iter().cloned().min().unwrap_or(0);
+            let top = heights.iter().cloned().max().unwrap_or(0);
+            let resting = heights.iter().filter(|&&z| z == bottom).count();
+
+            (resting, -((top - bottom) as i32))
+        })
+        .unwrap_or(CubeSymmetry::E0123)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::piece::Position;
+    use super::*;
+
+    fn domino() -> Template<(i8, i8, i8)> {
+        Template::new(vec!(Position::new(0, 0, 0), Position::new(1, 0, 0))).with_name("domino")
+    }
+
+    fn tromino() -> Template<(i8, i8, i8)> {
+        Template::new(vec!(Position::new(0, 0, 0), Position::new(1, 0, 0), Position::new(0, 1, 0)))
+    }
+
+    #[test]
+    fn stl_should_describe_a_closed_solid() {
+        let stl = PrintExporter::new().stl(&domino());
+
+        assert!(stl.starts_with("solid domino\n"));
+        assert!(stl.ends_with("endsolid domino\n"));
+        assert_eq!(stl.matches("facet normal").count(), 20);
+        assert!(stl.contains("vertex 20 10 10"));
+    }
+
+    #[test]
+    fn clearance_should_shrink_outer_faces() {
+        let stl = PrintExporter::new().with_clearance(1.0).stl(&domino());
+
+        assert!(stl.contains("vertex 0.5 0.5 0.5"));
+        assert!(stl.contains("vertex 19.5 9.5 9.5"));
+        assert!(stl.lines().filter(|line| line.contains("vertex")).all(|line| {
+            line.trim()[7..].split(' ').all(|c| (0.5..=19.5).contains(&c.parse::<f64>().unwrap()))
+        }));
+    }
+
+    #[test]
+    fn orientation_should_place_piece_on_the_bed() {
+        let stl = PrintExporter::new().with_orientation(CubeSymmetry::E1023).stl(&domino());
+
+        assert!(stl.contains("vertex 10 10 20"));
+        assert!(stl.lines().filter(|line| line.contains("vertex")).all(|line| !line.contains('-')));
+    }
+
+    #[test]
+    fn openscad_should_list_faces_of_a_polyhedron() {
+        let scad = PrintExporter::new().openscad(&domino());
+
+        assert!(scad.starts_with("// domino\npolyhedron(\n"));
+        assert_eq!(scad.matches("[0, 0, 0]").count(), 1);
+        assert!(!scad.contains("union"));
+    }
+
+    #[test]
+    fn openscad_should_add_fillets_in_concave_edges() {
+        let scad = PrintExporter::new().with_clearance(1.0).with_fillet(2.0).openscad(&tromino());
+
+        assert!(scad.starts_with("// piece\nunion() {\npolyhedron(\n"));
+        assert!(scad.ends_with("}\n"));
+        assert_eq!(scad.matches("multmatrix").count(), 1);
+        assert!(scad.contains("multmatrix([[1, 0, 0, 9.5], [0, 1, 0, 9.5], [0, 0, 1, 0.5], [0, 0, 0, 1]])"));
+        assert!(scad.contains("cube([2, 2, 9])"));
+    }
+
+    #[test]
+    fn flattest_orientation_should_lay_a_column_down() {
+        let column = Template::new(vec!(Position::new(0, 0, 0), Position::new(0, 0, 1), Position::new(0, 0, 2)));
+        let mut piece = Piece::new(column.iter().collect());
+
+        piece.transform(&flattest_orientation(&column));
+
+        assert!(piece.iter().all(|position| position.coordinates().2 == 0));
+    }
+}
@@ -4,6 +4,27 @@
 //! Packing problems are
 //! > a class of optimization problems in mathematics that involve attempting to
 //! > pack objects together into containers.
+//!
+//! # Serialization
+//! Enabling the `serde` feature derives `Serialize` and `Deserialize` for the
+//! puzzle types. Their JSON shape is part of the public interface.
+//!
+//! * A `Position` is an array of coordinates, e.g. `[1, 0, 2]`.
+//! * A `Template` and a `Piece` are objects with `positions` and an optional
+//!   `name`, e.g. `{"positions": [[0, 0], [1, 0]], "name": "I2"}`.
+//! * A `Bag` is an array of `[count, template]` pairs, with a `null` count
+//!   for an unlimited supply of the template.
+//! * A `Target` is an array of positions.
+//! * A `Solution` is an object with a `placements` array. Each placement has
+//!   a `piece` and, for pieces placed by the solver, the `template` index,
+//!   `symmetry` and `translation` that produced it.
+//! * A `Placement` is an object with the `template` index in the bag, the
+//!   `symmetry`, the `translation` and the placed `piece`.
+//! * A `Translation` is an array of coordinates and a `CubeSymmetry` is the
+//!   name of its variant, e.g. `"E1230"`.
 
+pub mod catalogue;
+pub mod export;
 pub mod puzzle;
+pub mod render;
 pub mod util;
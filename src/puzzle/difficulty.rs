@@ -0,0 +1,187 @@
+//! Rating the difficulty of a puzzle by measuring the search for solutions.
+//!
+//! The search is the one of the solver, which covers the minimum open
+//! `Position` first, but it explores the complete search tree. A puzzle for
+//! which the search visits many positions per solution is considered hard.
+
+use super::piece::{Normalizable, Transformable};
+use super::setup::Setup;
+use super::solver::{explore, orientations, validate, Solution, Step, Target, Visitor};
+use super::validation::PuzzleError;
+use super::vector::{Neighbours, VectorAdd, VectorDifference};
+
+/// The number of levels of the search tree for which the branching factor is reported.
+const EARLY_LEVELS: usize = 3;
+
+/// Scores below this are `Easy`. A score of 4 means 16 partial solutions per
+/// solution, so a solver hardly needs to backtrack.
+pub const MEDIUM_SCORE: f64 = 4.0;
+
+/// Scores from this on are `Hard`. A score of 8 means 256 partial solutions
+/// per solution, i.e. every solution is only reached after abandoning
+/// hundreds of dead ends.
+pub const HARD_SCORE: f64 = 8.0;
+
+/// How hard a puzzle is.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Difficulty {
+    /// A score below `MEDIUM_SCORE`.
+    Easy,
+    /// A score of at least `MEDIUM_SCORE`, but below `HARD_SCORE`.
+    Medium,
+    /// A score of at least `HARD_SCORE`.
+    Hard,
+}
+
+/// Measurements of the search for all solutions of a puzzle.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DifficultyReport {
+    nodes: usize,
+    forced_moves: usize,
+    branching: Vec<f64>,
+    solutions: usize,
+}
+
+impl DifficultyReport {
+    /// The number of partial solutions the search visited.
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// The number of partial solutions that allowed exactly one move.
+    pub fn forced_moves(&self) -> usize {
+        self.forced_moves
+    }
+
+    /// The average number of moves at each of the first levels of the
+    /// search tree, starting at the root.
+    pub fn branching(&self) -> &[f64] {
+        &self.branching
+    }
+
+    /// The number of solutions.
+    pub fn solutions(&self) -> usize {
+        self.solutions
+    }
+
+    /// The base two logarithm of the number of nodes per solution.
+    ///
+    /// Puzzles without a solution get the score of a puzzle with one.
+    pub fn score(&self) -> f64 {
+        (self.nodes as f64 / self.solutions.max(1) as f64).log2()
+    }
+
+    /// Grade the puzzle by its `score`.
+    pub fn grade(&self) -> Difficulty {
+        let score = self.score();
+        if score < MEDIUM_SCORE {
+            Difficulty::Easy
+        } else if score < HARD_SCORE {
+            Difficulty::Medium
+        } else {
+            Difficulty::Hard
+        }
+    }
+}
+
+/// Measure the search for all solutions of the `Setup`.
+pub fn difficulty<T>(setup: &Setup<T>) -> Result<DifficultyReport, PuzzleError<T>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    let mut measurements = Measurements { nodes: 0, forced_moves: 0, moves: vec!((0, 0); EARLY_LEVELS), solutions: 0 };
+    if !setup.target().is_packed() {
+        validate(setup.target(), setup.bag())?;
+        let orientations = orientations(setup.bag());
+        let mut counts: Vec<usize> = setup.bag().iter().map(|(count, _)| *count).collect();
+        explore(setup.target(), &orientations, &mut counts, Solution::empty(), &mut measurements);
+    } else {
+        measurements.nodes = 1;
+        measurements.solutions = 1;
+    }
+
+    Ok(DifficultyReport {
+        nodes: measurements.nodes,
+        forced_moves: measurements.forced_moves,
+        branching: measurements.moves
+            .iter()
+            .take_while(|(nodes, _)| *nodes > 0)
+            .map(|(nodes, moves)| *moves as f64 / *nodes as f64)
+            .collect(),
+        solutions: measurements.solutions,
+    })
+}
+
+/// Running totals of the search. The search starts from an empty `Solution`,
+/// so the number of pieces in a partial solution is its level in the tree.
+struct Measurements {
+    nodes: usize,
+    forced_moves: usize,
+    /// The number of nodes and the number of moves at the early levels.
+    moves: Vec<(usize, usize)>,
+    solutions: usize,
+}
+
+impl<T> Visitor<T> for Measurements where T: Clone + PartialOrd + Ord {
+    fn visit(&mut self, target: &Target<T>, partial_solution: &Solution<T>, _: &[usize], moves: usize) -> Step {
+        self.nodes += 1;
+        if target.is_packed() {
+            self.solutions += 1;
+            return Step::Prune;
+        }
+
+        if moves == 1 {
+            self.forced_moves += 1;
+        }
+        if let Some(level) = self.moves.get_mut(partial_solution.len()) {
+            level.0 += 1;
+            level.1 += moves;
+        }
+        Step::Descend
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::util::target::rectangle;
+    use super::super::piece::{Position, Template};
+    use super::super::pieces::Bag;
+    use super::*;
+
+    fn dominoes(count: usize) -> Bag<(i8, i8)> {
+        Bag::new(vec!((count, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0))))))
+    }
+
+    #[test]
+    fn should_measure_the_search_tree() {
+        let report = difficulty(&Setup::new(rectangle(3, 2), dominoes(3))).unwrap();
+
+        assert_eq!(report.solutions(), 3);
+        assert_eq!(report.nodes(), 9);
+        assert_eq!(report.forced_moves(), 4);
+        assert_eq!(report.branching(), &[2.0, 1.5, 1.0]);
+        assert_eq!(report.grade(), Difficulty::Easy);
+    }
+
+    #[test]
+    fn grades_should_start_at_their_thresholds() {
+        let report = |nodes| DifficultyReport { nodes, forced_moves: 0, branching: vec!(), solutions: 1 };
+
+        assert_eq!(report(15).grade(), Difficulty::Easy);
+        assert_eq!(report(16).grade(), Difficulty::Medium);
+        assert_eq!(report(255).grade(), Difficulty::Medium);
+        assert_eq!(report(256).grade(), Difficulty::Hard);
+    }
+
+    #[test]
+    fn harder_puzzles_should_score_higher() {
+        let bag = Bag::new(vec!(
+            (2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0), Position::d2(1, 1)))),
+            (2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(1, 1), Position::d2(2, 1)))),
+            (2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0), Position::d2(2, 1)))),
+        ));
+        let easy = difficulty(&Setup::new(rectangle(2, 1), dominoes(1))).unwrap();
+        let hard = difficulty(&Setup::new(rectangle(6, 4), bag)).unwrap();
+
+        assert!(easy.score() < hard.score());
+        assert_eq!(easy.grade(), Difficulty::Easy);
+        assert_eq!(hard.grade(), Difficulty::Medium);
+    }
+}
@@ -0,0 +1,156 @@
+//! Hints for players that are solving a puzzle piece by piece.
+//!
+//! The moves of a player are pinned in a `Setup`. A `Hint` tells whether the
+//! position can still be solved, how many completions there are and which
+//! move leads to one of them. The search stops as soon as it found the
+//! requested number of completions.
+//!
+//! ```
+//! use pack::puzzle::hint::hint;
+//! use pack::puzzle::piece::{CubeSymmetry, Position, Template, Translation};
+//! use pack::puzzle::pieces::Bag;
+//! use pack::puzzle::setup::Setup;
+//! use pack::util::target::rectangle;
+//!
+//! let domino = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+//! let setup = Setup::new(rectangle(2, 3), Bag::new(vec!((3, domino))))
+//!     .pin(0, CubeSymmetry::E0123, Translation::from((0, 0)))
+//!     .unwrap();
+//!
+//! let hint = hint(&setup, 10).unwrap();
+//! assert!(hint.is_solvable());
+//! assert_eq!(hint.completions(), 2);
+//! ```
+
+use super::piece::{Normalizable, Transformable};
+use super::setup::Setup;
+use super::solver::Placement;
+use super::validation::PuzzleError;
+use super::vector::{Neighbours, VectorAdd, VectorDifference};
+
+/// What is known about the completions of a partially solved puzzle.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Hint<T> {
+    completions: usize,
+    exhausted: bool,
+    next_move: Option<Placement<T>>,
+}
+
+impl<T> Hint<T> {
+    /// Determine if the position can still be completed.
+    pub fn is_solvable(&self) -> bool {
+        self.completions > 0
+    }
+
+    /// The number of completions found, at most the limit of the search.
+    pub fn completions(&self) -> usize {
+        self.completions
+    }
+
+    /// Determine if all completions were found. This is `false` when the
+    /// search reached its limit, even if there are no more completions.
+    pub fn is_exhaustive(&self) -> bool {
+        self.exhausted
+    }
+
+    /// A move that appears in a completion, or `None` when the position can
+    /// not be completed or is complete already.
+    ///
+    /// The move covers the minimum open `Position` of the `Target`.
+    pub fn next_move(&self) -> Option<&Placement<T>> {
+        self.next_move.as_ref()
+    }
+}
+
+/// Search for at most `limit` completions of the `Setup`.
+///
+/// A `limit` of one answers whether the position is solvable, and with a
+/// move, as fast as possible. A position in which the pieces left can not
+/// fill the open positions is not solvable.
+pub fn hint<T>(setup: &Setup<T>, limit: usize) -> Result<Hint<T>, PuzzleError<T>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    let moves = setup.pinned().placements().len();
+    let mut completions = 0;
+    let mut exhausted = true;
+    let mut next_move = None;
+    if limit > 0 {
+        let searched = setup.solve_while(&mut |solution| {
+            if next_move.is_none() {
+                next_move = solution.placements().get(moves).cloned();
+            }
+            completions += 1;
+            exhausted = completions < limit;
+            exhausted
+        });
+        match searched {
+            Ok(()) | Err(PuzzleError::VolumeMismatch { .. }) => {},
+            Err(error) => return Err(error),
+        }
+    } else {
+        exhausted = false;
+    }
+
+    Ok(Hint { completions, exhausted, next_move })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::util::target::rectangle;
+    use super::super::piece::{CubeSymmetry, Position, Template, Translation};
+    use super::super::pieces::Bag;
+    use super::*;
+
+    fn setup() -> Setup<(i8, i8)> {
+        let domino = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+
+        Setup::new(rectangle(4, 2), Bag::new(vec!((4, domino))))
+    }
+
+    #[test]
+    fn should_count_completions_up_to_the_limit() {
+        let all = hint(&setup(), 10).unwrap();
+        let capped = hint(&setup(), 2).unwrap();
+
+        assert_eq!(all.completions(), 5);
+        assert!(all.is_exhaustive());
+        assert_eq!(capped.completions(), 2);
+        assert!(!capped.is_exhaustive());
+    }
+
+    #[test]
+    fn next_move_should_lead_to_a_completion() {
+        let setup = setup().pin(0, CubeSymmetry::E0123, Translation::from((1, 0))).unwrap();
+
+        let first = hint(&setup, 1).unwrap();
+        let next_move = first.next_move().unwrap();
+        let second = hint(&setup.clone().pin_placement(next_move).unwrap(), 1).unwrap();
+
+        assert!(first.is_solvable());
+        assert_eq!(next_move.piece().iter().next(), Some(Position::d2(0, 0)));
+        assert!(second.is_solvable());
+    }
+
+    #[test]
+    fn should_detect_dead_ends() {
+        let setup = setup().block(vec!(Position::d2(0, 0), Position::d2(1, 1))).unwrap();
+
+        let hint = hint(&setup, 1).unwrap();
+
+        assert!(!hint.is_solvable());
+        assert!(hint.is_exhaustive());
+        assert!(hint.next_move().is_none());
+    }
+
+    #[test]
+    fn positions_without_enough_pieces_should_be_unsolvable() {
+        let domino = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+        let setup = Setup::new(rectangle(4, 2), Bag::new(vec!((3, domino))))
+            .pin(0, CubeSymmetry::E0123, Translation::from((0, 0)))
+            .unwrap();
+
+        let hint = hint(&setup, 1).unwrap();
+
+        assert!(!hint.is_solvable());
+        assert!(hint.is_exhaustive());
+        assert!(hint.next_move().is_none());
+    }
+}
@@ -1,6 +1,16 @@
 //! Describing and solving packing problems.
 
 mod vector;
+pub mod difficulty;
+pub mod hint;
+pub mod optimization;
 pub mod piece;
 pub mod pieces;
+pub mod shape;
+pub mod setup;
 pub mod solver;
+pub mod uniqueness;
+pub mod validation;
+pub mod verification;
+
+pub use self::vector::{Bounds, Neighbours, VectorAdd, VectorDifference};
@@ -0,0 +1,236 @@
+//! Packing as much as possible when the pieces can not fill the target.
+//!
+//! The search is a branch-and-bound: positions of the `Target` may be left
+//! empty, and branches that can not improve on the best packing found so far,
+//! even if every remaining piece would fit, are pruned.
+//!
+//! ```
+//! use pack::puzzle::optimization::{maximum_packing, Objective};
+//! use pack::puzzle::piece::{Position, Template};
+//! use pack::puzzle::pieces::Bag;
+//! use pack::util::target::rectangle;
+//!
+//! let domino = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+//! let packing = maximum_packing(&rectangle(3, 3), &Bag::new(vec!((5, domino))), Objective::Cells, None).unwrap();
+//!
+//! assert_eq!(packing.covered(), 8);
+//! assert!(packing.is_optimal());
+//! ```
+
+use super::piece::{Normalizable, Transformable};
+use super::pieces::Bag;
+use super::solver::{explore, orientations, Solution, Step, Target, Visitor};
+use super::validation::PuzzleError;
+use super::vector::{Neighbours, VectorAdd, VectorDifference};
+
+/// What a packing should maximize.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Objective {
+    /// The number of covered positions of the target.
+    Cells,
+    /// The number of placed pieces.
+    Pieces,
+}
+
+/// The best packing a search found.
+#[derive(Debug, Clone)]
+pub struct Packing<T> {
+    solution: Solution<T>,
+    covered: usize,
+    optimal: bool,
+}
+
+impl<T> Packing<T> where T: Clone {
+    /// The placed pieces.
+    pub fn solution(&self) -> &Solution<T> {
+        &self.solution
+    }
+
+    /// The number of covered positions of the target.
+    pub fn covered(&self) -> usize {
+        self.covered
+    }
+
+    /// The number of placed pieces.
+    pub fn pieces(&self) -> usize {
+        self.solution.len()
+    }
+
+    /// Determine if no packing is better, i.e. the search was not cut short.
+    pub fn is_optimal(&self) -> bool {
+        self.optimal
+    }
+}
+
+/// Find a packing of pieces from the `Bag` in the `Target` that maximizes
+/// the `Objective`. Positions of the `Target` may stay empty and pieces may
+/// stay in the `Bag`.
+///
+/// The search stops after visiting `node_limit` partial packings, if given,
+/// and reports the best packing found so far. Otherwise the packing is
+/// optimal.
+pub fn maximum_packing<T>(target: &Target<T>, bag: &Bag<T>, objective: Objective, node_limit: Option<usize>) -> Result<Packing<T>, PuzzleError<T>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    target.validate()?;
+    bag.validate()?;
+
+    let orientations = orientations(bag);
+    let mut counts: Vec<usize> = bag.iter().map(|(count, _)| *count).collect();
+    let mut search = Search {
+        volumes: bag.iter().map(|(_, template)| template.iter().count()).collect(),
+        counts: counts.clone(),
+        objective,
+        node_limit,
+        nodes: 0,
+        best: (0, Solution::empty()),
+        ceiling: 0,
+    };
+    search.ceiling = search.bound(&counts, target.iter().count(), 0);
+    let optimal = explore(target, &orientations, &mut counts, Solution::empty(), &mut search) || search.best.0 == search.ceiling;
+
+    let (_, solution) = search.best;
+    let covered = solution.iter().map(|piece| piece.iter().count()).sum();
+    Ok(Packing { solution, covered, optimal })
+}
+
+/// State of the branch-and-bound search.
+struct Search<T> {
+    /// The volume of the `Template`s, by index.
+    volumes: Vec<usize>,
+    /// The number of pieces in the `Bag` of the `Template`s, by index.
+    counts: Vec<usize>,
+    objective: Objective,
+    node_limit: Option<usize>,
+    nodes: usize,
+    /// The value of the best packing so far, and the packing itself.
+    best: (usize, Solution<T>),
+    /// The value no packing can exceed.
+    ceiling: usize,
+}
+
+impl<T> Search<T> {
+    /// An upper bound on the value of packings that extend a partial packing
+    /// of `value` with `open` positions and `counts` pieces left.
+    fn bound(&self, counts: &[usize], open: usize, value: usize) -> usize {
+        let pieces: Vec<usize> = (0..counts.len()).filter(|&index| counts[index] > 0).collect();
+        match self.objective {
+            Objective::Cells => {
+                let volume: usize = pieces.iter().map(|&index| counts[index].saturating_mul(self.volumes[index])).fold(0, usize::saturating_add);
+                value + open.min(volume)
+            },
+            Objective::Pieces => {
+                let count: usize = pieces.iter().map(|&index| counts[index]).fold(0, usize::saturating_add);
+                let smallest = pieces.iter().map(|&index| self.volumes[index]).min().unwrap_or(1).max(1);
+                value + count.min(open / smallest)
+            },
+        }
+    }
+
+    /// The value of a partial packing, given the number of pieces left.
+    fn value(&self, counts: &[usize]) -> usize {
+        (0..counts.len())
+            .map(|index| {
+                let placed = self.counts[index] - counts[index];
+                match self.objective {
+                    Objective::Cells => placed * self.volumes[index],
+                    Objective::Pieces => placed,
+                }
+            })
+            .sum()
+    }
+}
+
+impl<T> Visitor<T> for Search<T> where T: Clone + PartialOrd + Ord {
+    /// Record improvements, and prune branches that can not improve on the
+    /// best packing. Stops when the node limit or the ceiling is reached.
+    fn visit(&mut self, target: &Target<T>, partial_solution: &Solution<T>, counts: &[usize], _: usize) -> Step {
+        self.nodes += 1;
+        let value = self.value(counts);
+        if value > self.best.0 {
+            self.best = (value, partial_solution.clone());
+            if value == self.ceiling {
+                return Step::Stop;
+            }
+        }
+        if self.node_limit.map(|limit| self.nodes >= limit).unwrap_or(false) {
+            return Step::Stop;
+        }
+        let open = target.iter().count();
+        if open == 0 || self.bound(counts, open, value) <= self.best.0 {
+            return Step::Prune;
+        }
+        Step::DescendAndSkip
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::util::target::rectangle;
+    use super::super::piece::{Position, Template};
+    use super::super::verification::verify;
+    use super::*;
+
+    fn bag() -> Bag<(i8, i8)> {
+        Bag::new(vec!(
+            (2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(1, 1)))),
+            (3, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))),
+        ))
+    }
+
+    #[test]
+    fn should_cover_as_many_cells_as_possible() {
+        let target = rectangle(2, 2);
+        let bag = Bag::new(vec!((2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(1, 1))))));
+
+        let packing = maximum_packing(&target, &bag, Objective::Cells, None).unwrap();
+
+        assert_eq!(packing.covered(), 3);
+        assert_eq!(packing.pieces(), 1);
+        assert!(packing.is_optimal());
+    }
+
+    #[test]
+    fn should_place_as_many_pieces_as_possible() {
+        let target = rectangle(3, 3);
+
+        let cells = maximum_packing(&target, &bag(), Objective::Cells, None).unwrap();
+        let pieces = maximum_packing(&target, &bag(), Objective::Pieces, None).unwrap();
+
+        assert_eq!(cells.covered(), 9);
+        assert_eq!(pieces.pieces(), 4);
+        assert!(pieces.is_optimal());
+    }
+
+    #[test]
+    fn complete_packings_should_verify() {
+        let target = rectangle(3, 3);
+        let packing = maximum_packing(&target, &bag(), Objective::Cells, None).unwrap();
+
+        assert_eq!(verify(&target, &bag(), packing.solution()), Ok(()));
+    }
+
+    #[test]
+    fn packings_with_empty_cells_should_fit_without_overlap() {
+        let target = rectangle(3, 3);
+        let bag = Bag::new(vec!((5, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0))))));
+
+        let packing = maximum_packing(&target, &bag, Objective::Cells, None).unwrap();
+
+        assert_eq!(packing.pieces(), 4);
+        assert_eq!(packing.covered(), 8);
+        assert!(packing.solution().iter().all(|piece| target.fits(piece)));
+        assert_eq!(packing.solution().cells().len(), packing.covered());
+    }
+
+    #[test]
+    fn limited_searches_should_not_claim_optimality() {
+        let target = rectangle(3, 3);
+        let bag = Bag::new(vec!((4, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(1, 1))))));
+
+        let limited = maximum_packing(&target, &bag, Objective::Cells, Some(2)).unwrap();
+        let complete = maximum_packing(&target, &bag, Objective::Cells, None).unwrap();
+
+        assert!(!limited.is_optimal());
+        assert!(complete.is_optimal());
+        assert_eq!(complete.covered(), 6);
+    }
+}
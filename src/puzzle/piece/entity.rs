@@ -2,16 +2,39 @@
 
 use std::fmt::{Formatter, Display, Error};
 
-use super::super::vector::VectorAdd;
+use super::super::validation::{check_shape, PuzzleError};
+use super::super::vector::{Neighbours, VectorAdd};
 use super::{Position, MinimumPosition, Translatable, Translation, Transformable, CubeSymmetry};
 
 /// A piece that get packed.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "UnsortedPiece<T>", bound(deserialize = "T: serde::Deserialize<'de> + Ord + Clone")))]
 pub struct Piece<T> {
     positions: Vec<Position<T>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     name: Option<String>
 }
 
+/// Deserialized form of a `Piece`, before its `Position`s are sorted.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UnsortedPiece<T> {
+    positions: Vec<Position<T>>,
+    #[serde(default)]
+    name: Option<String>
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<UnsortedPiece<T>> for Piece<T> where T: PartialOrd + Ord + Clone {
+    fn from(piece: UnsortedPiece<T>) -> Self {
+        match piece.name {
+            Some(name) => Piece::named(piece.positions, name),
+            None => Piece::new(piece.positions),
+        }
+    }
+}
+
 impl<T> Piece<T> where T: PartialOrd + Ord + Clone {
     /// Create a new `Piece` from a collection of `Position`s.
     pub fn new(mut positions: Vec<Position<T>>) -> Piece<T> {
@@ -25,6 +48,11 @@ impl<T> Piece<T> where T: PartialOrd + Ord + Clone {
         Piece { positions, name: Some(name.into()) }
     }
 
+    /// The name of this `Piece`, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     /// Determine if a `Position` is contained in this `Piece`.
     pub fn contains(&self, position: &Position<T>) -> bool {
         self.positions.contains(position)
@@ -37,6 +65,22 @@ impl<T> Piece<T> where T: PartialOrd + Ord + Clone {
     }
 }
 
+impl<T> Piece<T> where T: PartialOrd + Ord + Clone + Neighbours<T> {
+    /// Create a new `Piece` from a collection of `Position`s, provided they
+    /// form a valid shape.
+    pub fn try_new(positions: Vec<Position<T>>) -> Result<Piece<T>, PuzzleError<T>> {
+        check_shape(&positions)?;
+
+        Ok(Piece::new(positions))
+    }
+
+    /// Check that this `Piece` is a valid shape, i.e. has distinct `Position`s
+    /// that are connected through faces.
+    pub fn validate(&self) -> Result<(), PuzzleError<T>> {
+        check_shape(&self.positions)
+    }
+}
+
 impl Display for Piece<(i8,i8,i8)> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "[")?;
@@ -71,6 +115,23 @@ impl<T> Transformable for Piece<T> where T: Transformable + PartialOrd + Ord {
     }
 }
 
+impl<T> Piece<T> where T: VectorAdd<T> {
+    /// Move this `Piece` by the `translation`, provided every `Position` stays
+    /// in the range of the coordinates. Otherwise the `Piece` is left as is,
+    /// and the first `Position` that can not be moved is returned.
+    pub fn checked_translate(&mut self, translation: &Translation<T>) -> Result<(), Position<T>> where T: Clone {
+        let mut moved: Vec<Position<T>> = vec!();
+        for position in &self.positions {
+            match position.coordinates().checked_add(&translation.delta) {
+                Some(coordinates) => moved.push(Position::from(coordinates)),
+                None => return Err(position.clone()),
+            }
+        }
+        self.positions = moved;
+        Ok(())
+    }
+}
+
 impl<T> Translatable<T> for Piece<T> where T: VectorAdd<T>  {
     fn translate(&mut self, translation: &Translation<T>) {
         for position in &mut self.positions {
@@ -11,5 +11,5 @@ mod template;
 pub use self::symmetry::{Transformable, CubeSymmetry, CubeSymmetryIterator};
 pub use self::translation::{Translatable, Translation};
 pub use self::position::{Position, Positionable, Normalizable, MinimumPosition};
-pub use self::entity::{Piece};
+pub use self::entity::{Piece, PositionIterator};
 pub use self::template::Template;
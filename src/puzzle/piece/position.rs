@@ -7,6 +7,7 @@ use super::{Transformable, CubeSymmetry, Translatable, Translation};
 
 /// Position of a cubelet.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 pub struct Position<T> {
     base: T,
 }
@@ -25,6 +26,19 @@ impl Position<(i8, i8)> {
     }
 }
 
+impl<T> From<T> for Position<T> {
+    fn from(base: T) -> Self {
+        Position { base }
+    }
+}
+
+impl<T> Position<T> {
+    /// The coordinates of this `Position`.
+    pub fn coordinates(&self) -> &T {
+        &self.base
+    }
+}
+
 
 /// Move an entity to certain `Position`.
 pub trait Positionable<T> {
@@ -94,7 +108,7 @@ impl<T> PartialOrd for Position<T> where T: PartialOrd {
 
 impl<T> Transformable for Position<T> where T: Transformable {
     fn transform(&mut self, symmetry: &CubeSymmetry) {
-        self.base.transform(&symmetry);
+        self.base.transform(symmetry);
     }
 }
 
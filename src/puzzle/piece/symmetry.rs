@@ -4,13 +4,22 @@
 pub trait Transformable {
     /// Apply a symmetry and transform the entity.
     fn transform(&mut self, symmetry: &CubeSymmetry);
+
+    /// Whether `symmetry` is a symmetry of the space the entity lives in.
+    ///
+    /// Planar entities only admit the symmetries of the cube that keep the
+    /// plane in place, i.e. the symmetries of the square.
+    fn admits(_symmetry: &CubeSymmetry) -> bool where Self: Sized {
+        true
+    }
 }
 
 /// Symmetries of the Cube.
 ///
 /// The group of symmetries of the cube is isomorphic to S<sub>4</sub>. Here we
 /// use that fact as a naming convention for our elements.
-#[derive(Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CubeSymmetry {
     #[allow(missing_docs)]
     E0123,
@@ -77,6 +86,12 @@ impl CubeSymmetryIterator {
     }
 }
 
+impl Default for CubeSymmetryIterator {
+    fn default() -> Self {
+        CubeSymmetryIterator::new()
+    }
+}
+
 impl Iterator for CubeSymmetryIterator {
     type Item = CubeSymmetry;
 
@@ -160,4 +175,34 @@ impl Transformable for (i8, i8) {
         self.0 = v.0;
         self.1 = v.1;
     }
+
+    fn admits(symmetry: &CubeSymmetry) -> bool {
+        matches!(
+            *symmetry,
+            CubeSymmetry::E0123 | CubeSymmetry::E0321 | CubeSymmetry::E1032 | CubeSymmetry::E1230 |
+            CubeSymmetry::E2103 | CubeSymmetry::E2301 | CubeSymmetry::E3012 | CubeSymmetry::E3210
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use super::*;
+
+    #[test]
+    fn planar_symmetries_should_not_collapse_cells() {
+        let cells: Vec<(i8, i8)> = vec!((0, 0), (1, 0), (2, 0), (2, 1));
+
+        let mut admitted = 0;
+        for symmetry in CubeSymmetryIterator::new() {
+            let image: HashSet<(i8, i8)> = cells.iter().map(|cell| { let mut cell = *cell; cell.transform(&symmetry); cell }).collect();
+
+            assert_eq!(image.len() == cells.len(), <(i8, i8)>::admits(&symmetry));
+            if <(i8, i8)>::admits(&symmetry) {
+                admitted += 1;
+            }
+        }
+        assert_eq!(admitted, 8);
+    }
 }
@@ -1,14 +1,17 @@
 //! template is a container to hold orientations of pieces.
 
 use std::convert::From;
-use super::super::vector::VectorAdd;
-use super::{Position, Normalizable, Piece, CubeSymmetryIterator, Translatable, Transformable, MinimumPosition};
+use super::super::validation::{check_shape, PuzzleError};
+use super::super::vector::{Neighbours, VectorAdd};
+use super::{Position, PositionIterator, Normalizable, Piece, CubeSymmetry, CubeSymmetryIterator, Translatable, Transformable, MinimumPosition};
 
 /// A `Template` is a container to hold a representation of a `Piece`. By
 /// Iterating over a one gets a piece in all the possible orientations.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Template<T> {
     positions: Vec<Position<T>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     name: Option<String>
 }
 
@@ -24,6 +27,76 @@ impl<T> Template<T> {
 
         Template { positions: self.positions, name }
     }
+
+    /// The name of this `Template`, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl<T> Template<T> where T: Clone {
+    /// Create an `Iterator` that iterates over all `Position`s.
+    pub fn iter(&self) -> PositionIterator<T> {
+        PositionIterator::new(self.positions.to_vec())
+    }
+}
+
+impl<T> Template<T> where T: Clone + Ord + Neighbours<T> {
+    /// Create a `Template` from a vector of `Position`s, provided they form a
+    /// valid shape.
+    pub fn try_new(positions: Vec<Position<T>>) -> Result<Template<T>, PuzzleError<T>> {
+        check_shape(&positions)?;
+
+        Ok(Template::new(positions))
+    }
+
+    /// Check that this `Template` is a valid shape, i.e. has distinct
+    /// `Position`s that are connected through faces.
+    pub fn validate(&self) -> Result<(), PuzzleError<T>> {
+        check_shape(&self.positions)
+    }
+}
+
+impl<T> Template<T> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorAdd<T> {
+    /// The distinct orientations of this `Template`, each with the first
+    /// `CubeSymmetry` that produces it.
+    ///
+    /// The `Piece`s are translated so that their minimum `Position` is the
+    /// reference `Position`, in the same order as iterating the `Template`.
+    pub fn orientations(&self) -> Vec<(CubeSymmetry, Piece<T>)> {
+        let mut iterator = PieceIterator::new(self.clone());
+        let mut orientations = vec!();
+        while let Some(orientation) = iterator.next_orientation() {
+            orientations.push(orientation);
+        }
+        orientations
+    }
+}
+
+impl<T> Template<T> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorAdd<T> {
+    /// The canonical form of this `Template`, i.e. the least of its
+    /// orientations, with its minimum `Position` at the reference `Position`.
+    ///
+    /// Congruent `Template`s have the same canonical form. The name is kept.
+    pub fn canonical(&self) -> Template<T> {
+        if self.positions.is_empty() {
+            return self.clone();
+        }
+        let positions = self.orientations()
+            .into_iter()
+            .map(|(_, piece)| piece.iter().collect::<Vec<Position<T>>>())
+            .min()
+            .unwrap();
+
+        Template { positions, name: self.name.clone() }
+    }
+
+    /// Determine if `other` is this `Template` up to a translation and a
+    /// symmetry the space admits, i.e. a rotation, or in the plane a rotation
+    /// or a reflection. Names are ignored.
+    pub fn is_congruent(&self, other: &Template<T>) -> bool {
+        self.canonical().positions == other.canonical().positions
+    }
 }
 
 impl<T> IntoIterator for Template<T> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorAdd<T> {
@@ -55,13 +128,12 @@ impl<T> PieceIterator<T> {
     }
 }
 
-impl<T> Iterator for PieceIterator<T> where T: Clone + PartialEq + Eq + PartialOrd + Ord + Transformable + Normalizable<T> + VectorAdd<T> {
-    type Item = Piece<T>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl<T> PieceIterator<T> where T: Clone + PartialEq + Eq + PartialOrd + Ord + Transformable + Normalizable<T> + VectorAdd<T> {
+    /// The next orientation, together with the `CubeSymmetry` that produces it.
+    fn next_orientation(&mut self) -> Option<(CubeSymmetry, Piece<T>)> {
         let mut symmetry_option = self.symmetry_iterator.next();
         while symmetry_option.is_some() {
-            let piece_option = symmetry_option.map(|symmetry|{
+            let piece_option = symmetry_option.filter(T::admits).map(|symmetry|{
                 let mut piece = Piece::from(self.template.clone());
 
                 piece.transform(&symmetry);
@@ -69,16 +141,15 @@ impl<T> Iterator for PieceIterator<T> where T: Clone + PartialEq + Eq + PartialO
                 let translation = minimum_position.unwrap().to_reference();
                 piece.translate(&translation);
 
-                piece
+                (symmetry, piece)
             });
 
-            if piece_option.is_some() {
-                let piece = piece_option.unwrap();
+            if let Some((symmetry, piece)) = piece_option {
                 let clone = piece.clone();
                 if !self.seen_pieces.contains(&clone) {
                     self.seen_pieces.push(clone);
 
-                    return Some(piece)
+                    return Some((symmetry, piece))
                 }
             }
 
@@ -88,18 +159,26 @@ impl<T> Iterator for PieceIterator<T> where T: Clone + PartialEq + Eq + PartialO
     }
 }
 
+impl<T> Iterator for PieceIterator<T> where T: Clone + PartialEq + Eq + PartialOrd + Ord + Transformable + Normalizable<T> + VectorAdd<T> {
+    type Item = Piece<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_orientation().map(|(_, piece)| piece)
+    }
+}
+
 impl<T> From<Template<T>> for Piece<T> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorAdd<T> {
     fn from(template: Template<T>) -> Self {
-        if template.name.is_some() {
-            Piece::named(template.positions, template.name.unwrap())
-        } else {
-            Piece::new(template.positions)
+        match template.name {
+            Some(name) => Piece::named(template.positions, name),
+            None => Piece::new(template.positions),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
     use std::iter::Iterator;
     use super::*;
 
@@ -146,4 +225,59 @@ mod tests {
 
         assert_eq!(iterator.count(), 3);
     }
+
+    #[test]
+    fn orientations_should_record_their_symmetry() {
+        let template = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+
+        let orientations = template.orientations();
+
+        assert_eq!(orientations.len(), 2);
+        assert_eq!(orientations[0], (CubeSymmetry::E0123, Piece::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))));
+        assert_eq!(orientations[1].1, Piece::new(vec!(Position::d2(0, 0), Position::d2(0, 1))));
+    }
+
+    #[test]
+    fn try_new_should_refuse_invalid_shapes() {
+        assert!(Template::try_new(vec!(Position::d2(0, 0), Position::d2(1, 0))).is_ok());
+        assert_eq!(Template::try_new(vec!(Position::d2(0, 0), Position::d2(2, 0))), Err(PuzzleError::Disconnected));
+    }
+
+    #[test]
+    fn planar_templates_should_return_the_orientations_in_the_plane() {
+        let template = Template::new(vec!(
+            Position::d2(0, 0),
+            Position::d2(1, 0),
+            Position::d2(2, 0),
+            Position::d2(2, 1),
+        ));
+
+        let pieces: Vec<Piece<(i8, i8)>> = template.into_iter().collect();
+
+        assert_eq!(pieces.len(), 8);
+        assert!(pieces.iter().all(|piece| piece.iter().collect::<HashSet<Position<(i8, i8)>>>().len() == 4));
+    }
+
+    #[test]
+    fn congruent_templates_should_have_the_same_canonical_form() {
+        let l = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0), Position::d2(2, 1))).with_name("L");
+        let mirrored = Template::new(vec!(Position::d2(3, 3), Position::d2(3, 4), Position::d2(3, 5), Position::d2(2, 5)));
+        let straight = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0), Position::d2(3, 0)));
+
+        let canonical = Template::new(vec!(Position::d2(0, 0), Position::d2(0, 1), Position::d2(0, 2), Position::d2(1, 0))).with_name("L");
+
+        assert_eq!(l.canonical(), canonical);
+        assert!(mirrored.canonical().iter().eq(canonical.iter()));
+        assert!(l.is_congruent(&mirrored));
+        assert!(!l.is_congruent(&straight));
+    }
+
+    #[test]
+    fn spatial_templates_should_not_be_congruent_to_their_mirror_image() {
+        let screw = Template::new(vec!(Position::new(0, 0, 0), Position::new(1, 0, 0), Position::new(1, 1, 0), Position::new(1, 1, 1)));
+        let mirrored = Template::new(vec!(Position::new(0, 0, 0), Position::new(-1, 0, 0), Position::new(-1, 1, 0), Position::new(-1, 1, 1)));
+
+        assert!(screw.is_congruent(&screw.canonical()));
+        assert!(!screw.is_congruent(&mirrored));
+    }
 }
@@ -3,7 +3,8 @@
 use super::super::vector::{VectorDifference, VectorAdd};
 
 /// Entities can be translated through space. This struct determines how.
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 pub struct Translation<T> {
     /// By how much an entity gets translated in space.
     pub delta : T,
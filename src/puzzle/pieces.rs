@@ -1,19 +1,129 @@
 //! Containers that can dispense `Template`s.
 
-use super::piece::Template;
+use super::piece::{Normalizable, Template, Transformable};
+use super::validation::PuzzleError;
+use super::vector::{Neighbours, VectorAdd};
+
+/// The count of a `Template` of which a `Bag` holds as many pieces as needed.
+pub const UNLIMITED: usize = usize::MAX;
 
 /// A container for `Template`s. Iterating over a `Bag` provides access to a
 /// tuple of a `Template` and the rest of the `Bag`.
+///
+/// Every `Template` has a count, the number of pieces of it in the `Bag`, or
+/// `UNLIMITED`. A `Bag` built with `normalized` holds every shape once.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 pub struct Bag<T> {
-    collection: Vec<(u8, Template<T>)>,
+    #[cfg_attr(feature = "serde", serde(with = "counts"))]
+    #[cfg_attr(feature = "serde", serde(bound(serialize = "T: serde::Serialize", deserialize = "T: serde::Deserialize<'de>")))]
+    collection: Vec<(usize, Template<T>)>,
 }
 
 impl<T> Bag<T> {
     /// Create a `Bag` from a collection of Templates
-    pub fn new(collection: Vec<(u8, Template<T>)>) -> Self {
+    pub fn new(collection: Vec<(usize, Template<T>)>) -> Self {
         Self { collection }
     }
+
+    /// Create a `Bag` with an `UNLIMITED` count of every `Template`.
+    pub fn unlimited(templates: Vec<Template<T>>) -> Self {
+        Self { collection: templates.into_iter().map(|template| (UNLIMITED, template)).collect() }
+    }
+
+    /// The number of pieces in this `Bag`, or `UNLIMITED`.
+    pub fn pieces(&self) -> usize {
+        self.collection.iter().fold(0, |total: usize, (count, _)| total.saturating_add(*count))
+    }
+
+    /// Create an `Iterator` over the `Template`s in this `Bag`, with their counts.
+    pub fn iter(&self) -> std::slice::Iter<'_, (usize, Template<T>)> {
+        self.collection.iter()
+    }
+}
+
+impl<T> Bag<T> where T: Clone {
+    /// Create a `Bag` with one piece of the `Template` at `index` taken out,
+    /// or `None` when there is no such `Template` or no pieces are left.
+    ///
+    /// The `Template` keeps its index, even when no pieces are left. An
+    /// `UNLIMITED` count stays unlimited.
+    pub fn take(&self, index: usize) -> Option<Bag<T>> {
+        match self.collection.get(index) {
+            Some((count, _)) if *count > 0 => {
+                let mut collection = self.collection.to_vec();
+                if *count != UNLIMITED {
+                    collection[index].0 -= 1;
+                }
+
+                Some(Bag::new(collection))
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<T> Bag<T> where T: Clone + Ord + Transformable + Normalizable<T> + VectorAdd<T> {
+    /// Create a `Bag` from a collection of Templates, brought in their
+    /// canonical form. Entries with congruent `Template`s that have the same
+    /// name are merged into a single entry, of which the count is the sum of
+    /// their counts.
+    pub fn normalized(collection: Vec<(usize, Template<T>)>) -> Self {
+        Bag::new(collection).merged()
+    }
+
+    /// This `Bag` with the `Template`s in their canonical form, and entries
+    /// with congruent `Template`s that have the same name merged, as in
+    /// `normalized`.
+    ///
+    /// *Note* the indices of the `Template`s change when entries are merged.
+    pub fn merged(&self) -> Bag<T> {
+        let mut collection: Vec<(usize, Template<T>)> = vec!();
+        for (count, template) in &self.collection {
+            let canonical = template.canonical();
+            match collection.iter_mut().find(|(_, other)| *other == canonical) {
+                Some(entry) => entry.0 = entry.0.saturating_add(*count),
+                None => collection.push((*count, canonical)),
+            }
+        }
+        Bag { collection }
+    }
+
+    /// For every `Template`, the index of the first `Template` that is
+    /// congruent to it and has the same name.
+    pub(crate) fn congruence(&self) -> Vec<usize> {
+        let canonicals: Vec<Template<T>> = self.collection.iter().map(|(_, template)| template.canonical()).collect();
+
+        canonicals.iter()
+            .map(|canonical| canonicals.iter().position(|other| other == canonical).unwrap())
+            .collect()
+    }
+}
+
+impl<T> Bag<T> where T: Clone + Ord + Neighbours<T> {
+    /// Create a `Bag` from a collection of Templates, provided all `Template`s are valid.
+    pub fn try_new(collection: Vec<(usize, Template<T>)>) -> Result<Self, PuzzleError<T>> {
+        let bag = Bag::new(collection);
+        bag.validate()?;
+
+        Ok(bag)
+    }
+
+    /// Check that all `Template`s in this `Bag` are valid.
+    pub fn validate(&self) -> Result<(), PuzzleError<T>> {
+        for (index, (_, template)) in self.collection.iter().enumerate() {
+            template.validate().map_err(|error| PuzzleError::InvalidTemplate { index, error: Box::new(error) })?;
+        }
+        Ok(())
+    }
+
+    /// The number of `Position`s covered by all pieces in this `Bag`, or
+    /// `UNLIMITED` when there is no limit.
+    pub fn volume(&self) -> usize {
+        self.collection
+            .iter()
+            .fold(0, |total: usize, (count, template)| total.saturating_add(count.saturating_mul(template.iter().count())))
+    }
 }
 
 impl<T> IntoIterator for Bag<T> where T: Clone {
@@ -28,7 +138,7 @@ impl<T> IntoIterator for Bag<T> where T: Clone {
 
 /// Iterator over tuples of `Template`s and rest of `Bag`s.
 pub struct BagSelectionIterator<T> {
-    collection: Vec<(u8, Template<T>)>,
+    collection: Vec<(usize, Template<T>)>,
     index: usize,
 }
 
@@ -43,9 +153,11 @@ impl<T> Iterator for BagSelectionIterator<T> where T: Clone {
 
     fn next(&mut self) -> Option<(Template<T>, Bag<T>)> {
         if self.index < self.collection.len() {
-            let mut collection: Vec<(u8, Template<T>)> = self.collection.to_vec();
+            let mut collection: Vec<(usize, Template<T>)> = self.collection.to_vec();
             let template;
-            if collection[self.index].0 > 1 {
+            if collection[self.index].0 == UNLIMITED {
+                template = collection[self.index].1.clone();
+            } else if collection[self.index].0 > 1 {
                 template = collection[self.index].1.clone();
                 collection[self.index].0 -= 1;
             } else {
@@ -60,6 +172,29 @@ impl<T> Iterator for BagSelectionIterator<T> where T: Clone {
     }
 }
 
+/// Serialize counts as numbers, and `UNLIMITED` counts as `null`.
+#[cfg(feature = "serde")]
+mod counts {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{Template, UNLIMITED};
+
+    pub fn serialize<S, T>(collection: &[(usize, Template<T>)], serializer: S) -> Result<S::Ok, S::Error> where S: Serializer, T: Serialize {
+        let entries: Vec<(Option<usize>, &Template<T>)> = collection
+            .iter()
+            .map(|(count, template)| (if *count == UNLIMITED { None } else { Some(*count) }, template))
+            .collect();
+
+        entries.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<(usize, Template<T>)>, D::Error> where D: Deserializer<'de>, T: Deserialize<'de> {
+        let entries: Vec<(Option<usize>, Template<T>)> = Vec::deserialize(deserializer)?;
+
+        Ok(entries.into_iter().map(|(count, template)| (count.unwrap_or(UNLIMITED), template)).collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::iter::IntoIterator;
@@ -89,4 +224,95 @@ mod tests {
                 Position::new(1, 0, 0)))))));
 
     }
+
+    #[test]
+    fn take_should_keep_indices() {
+        let bag = Bag::new(vec!(
+            (1, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))),
+            (1, Template::new(vec!(Position::d2(0, 0)))),
+        ));
+
+        let rest = bag.take(0).unwrap();
+
+        assert_eq!(rest.iter().map(|(count, _)| *count).collect::<Vec<usize>>(), vec!(0, 1));
+        assert_eq!(rest.volume(), 1);
+        assert!(rest.take(0).is_none());
+        assert!(rest.take(2).is_none());
+    }
+
+    #[test]
+    fn unlimited_bags_should_never_run_out() {
+        let bag = Bag::unlimited(vec!(Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))));
+
+        let rest = bag.take(0).unwrap();
+
+        assert_eq!(rest, bag);
+        assert_eq!(rest.pieces(), UNLIMITED);
+        assert_eq!(rest.volume(), UNLIMITED);
+        assert_eq!(Bag::new(vec!((300, Template::new(vec!(Position::d2(0, 0)))))).volume(), 300);
+    }
+
+    #[test]
+    fn bag_should_report_invalid_templates() {
+        let bag = Bag::try_new(vec!(
+            (1, Template::new(vec!(Position::d2(0, 0)))),
+            (2, Template::new(vec!(Position::d2(0, 0), Position::d2(0, 0)))),
+        ));
+
+        assert_eq!(bag, Err(PuzzleError::InvalidTemplate { index: 1, error: Box::new(PuzzleError::DuplicatePosition(Position::d2(0, 0))) }));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bag_should_serialize_as_list_of_counted_templates() {
+        let bag = Bag::new(vec!(
+            (2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0))).with_name("I2")),
+            (1, Template::new(vec!(Position::d2(0, 0)))),
+        ));
+
+        let json = serde_json::to_string(&bag).expect("to serialize bag");
+        assert_eq!(json, r#"[[2,{"positions":[[0,0],[1,0]],"name":"I2"}],[1,{"positions":[[0,0]]}]]"#);
+
+        let copy: Bag<(i8, i8)> = serde_json::from_str(&json).expect("to deserialize bag");
+        assert_eq!(copy, bag);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn unlimited_counts_should_serialize_as_null() {
+        let bag = Bag::new(vec!((UNLIMITED, Template::new(vec!(Position::d2(0, 0)))), (300, Template::new(vec!(Position::d2(0, 0))))));
+
+        let json = serde_json::to_string(&bag).expect("to serialize bag");
+        assert_eq!(json, r#"[[null,{"positions":[[0,0]]}],[300,{"positions":[[0,0]]}]]"#);
+
+        let copy: Bag<(i8, i8)> = serde_json::from_str(&json).expect("to deserialize bag");
+        assert_eq!(copy, bag);
+    }
+
+    #[test]
+    fn congruent_templates_should_be_merged() {
+        let bag = Bag::new(vec!(
+            (1, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0), Position::d2(2, 1)))),
+            (2, Template::new(vec!(Position::d2(5, 5), Position::d2(5, 6), Position::d2(5, 7), Position::d2(4, 7)))),
+            (1, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))),
+        ));
+
+        let merged = bag.merged();
+
+        assert_eq!(bag.iter().count(), 3);
+        assert_eq!(merged.iter().map(|(count, _)| *count).collect::<Vec<usize>>(), vec!(3, 1));
+        assert_eq!(merged.volume(), 14);
+    }
+
+    #[test]
+    fn differently_named_templates_should_not_be_merged() {
+        let bag = Bag::new(vec!(
+            (1, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0))).with_name("red")),
+            (1, Template::new(vec!(Position::d2(0, 0), Position::d2(0, 1))).with_name("blue")),
+            (UNLIMITED, Template::new(vec!(Position::d2(0, 0), Position::d2(0, 1))).with_name("red")),
+        ));
+
+        assert_eq!(bag.congruence(), vec!(0, 1, 0));
+        assert_eq!(bag.merged().iter().map(|(count, template)| (*count, template.name())).collect::<Vec<(usize, Option<&str>)>>(), vec!((UNLIMITED, Some("red")), (1, Some("blue"))));
+    }
 }
@@ -0,0 +1,221 @@
+//! Packing problems with pieces pinned in place before solving.
+//!
+//! Puzzle challenges often fix some pieces, or block some cells, and ask to
+//! solve the rest.
+//!
+//! ```
+//! use pack::puzzle::piece::{CubeSymmetry, Position, Template, Translation};
+//! use pack::puzzle::pieces::Bag;
+//! use pack::puzzle::setup::Setup;
+//! use pack::util::target::rectangle;
+//!
+//! let domino = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+//! let setup = Setup::new(rectangle(2, 3), Bag::new(vec!((3, domino))))
+//!     .pin(0, CubeSymmetry::E0123, Translation::from((0, 1)))
+//!     .unwrap();
+//!
+//! let mut count = 0;
+//! setup.solve(&mut |_| count += 1).unwrap();
+//! assert_eq!(count, 1);
+//! ```
+
+use super::piece::{CubeSymmetry, Normalizable, Piece, Position, Transformable, Translation};
+use super::pieces::Bag;
+use super::solver::{search, validate, Placement, Solution, Target};
+use super::validation::PuzzleError;
+use super::vector::{Neighbours, VectorAdd, VectorDifference};
+
+/// A packing problem with pieces pinned in place and cells blocked.
+///
+/// Pinned pieces and blocked cells are removed from the `Target`, pinned
+/// pieces are taken out of the `Bag` as well. Solutions include the pinned
+/// pieces, with their `Placement`, but not the blocked cells.
+#[derive(Debug, Clone)]
+pub struct Setup<T> {
+    target: Target<T>,
+    bag: Bag<T>,
+    pinned: Solution<T>,
+    blocked: Vec<Position<T>>,
+}
+
+impl<T> Setup<T> {
+    /// The positions of the `Target` that are still open.
+    pub fn target(&self) -> &Target<T> {
+        &self.target
+    }
+
+    /// The pieces that are not pinned.
+    pub fn bag(&self) -> &Bag<T> {
+        &self.bag
+    }
+
+    /// The pinned pieces.
+    pub fn pinned(&self) -> &Solution<T> {
+        &self.pinned
+    }
+
+    /// The blocked positions.
+    pub fn blocked(&self) -> &[Position<T>] {
+        &self.blocked
+    }
+}
+
+impl<T> Setup<T> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    /// Create a `Setup` for packing the `Target` with pieces from the `Bag`.
+    pub fn new(target: Target<T>, bag: Bag<T>) -> Setup<T> {
+        Setup { target, bag, pinned: Solution::empty(), blocked: vec!() }
+    }
+
+    /// Block `positions` of the `Target`, so that no piece covers them.
+    ///
+    /// Fails when a position is not open in the `Target`.
+    pub fn block(self, positions: Vec<Position<T>>) -> Result<Setup<T>, PuzzleError<T>> {
+        let mut setup = self;
+        for position in positions {
+            let piece = Piece::new(vec!(position.clone()));
+            if !setup.target.fits(&piece) {
+                return Err(PuzzleError::Obstructed(position));
+            }
+            setup.target = setup.target.place(&piece);
+            setup.blocked.push(position);
+        }
+        Ok(setup)
+    }
+
+    /// Pin a piece of the `Template` at index `template` of the `Bag`,
+    /// transformed by `symmetry` and then moved by `translation`.
+    ///
+    /// Fails when there is no such `Template`, when no pieces of it are
+    /// left, when the space does not admit the `symmetry` or when the piece
+    /// does not fit in the open positions of the `Target`, e.g. because the
+    /// `translation` moves it beyond the range of the coordinates.
+    pub fn pin(self, template: usize, symmetry: CubeSymmetry, translation: Translation<T>) -> Result<Setup<T>, PuzzleError<T>> {
+        let mut piece = match self.bag.iter().nth(template) {
+            Some((_, entry)) => Piece::from(entry.clone()),
+            None => return Err(PuzzleError::UnknownTemplate(template)),
+        };
+        let bag = self.bag.take(template).ok_or(PuzzleError::TemplateUsedUp(template))?;
+        if !T::admits(&symmetry) {
+            return Err(PuzzleError::InadmissibleSymmetry(symmetry));
+        }
+        piece.transform(&symmetry);
+        piece.checked_translate(&translation).map_err(PuzzleError::Obstructed)?;
+        if let Some(position) = piece.iter().find(|position| !self.target.fits(&Piece::new(vec!(position.clone())))) {
+            return Err(PuzzleError::Obstructed(position));
+        }
+
+        let target = self.target.place(&piece);
+        let pinned = self.pinned.place(&Placement::new(template, symmetry, translation, piece));
+        Ok(Setup { target, bag, pinned, blocked: self.blocked })
+    }
+
+    /// Pin a piece as described by a `Placement`, e.g. a move of a player.
+    ///
+    /// The `Piece` of the `Placement` is ignored, it is derived from the
+    /// `Template`, the symmetry and the translation instead. A `Placement`
+    /// that lacks any of these is refused.
+    pub fn pin_placement(self, placement: &Placement<T>) -> Result<Setup<T>, PuzzleError<T>> {
+        match (placement.template(), placement.symmetry(), placement.translation()) {
+            (Some(template), Some(symmetry), Some(translation)) => self.pin(template, symmetry.clone(), translation.clone()),
+            _ => Err(PuzzleError::IncompletePlacement),
+        }
+    }
+
+    /// Pack the open positions with the remaining pieces. When a solution is
+    /// found, the `when_solved` callback is called with that solution,
+    /// including the pinned pieces.
+    pub fn solve<F>(&self, when_solved: &mut F) -> Result<(), PuzzleError<T>> where F: FnMut(Solution<T>) {
+        self.solve_while(&mut |solution| { when_solved(solution); true })
+    }
+
+    /// Variant of the `solve` method that stops searching as soon as the
+    /// `when_solved` callback returns `false`.
+    pub fn solve_while<F>(&self, when_solved: &mut F) -> Result<(), PuzzleError<T>> where F: FnMut(Solution<T>) -> bool {
+        if self.target.is_packed() {
+            self.bag.validate()?;
+            when_solved(self.pinned.clone());
+            return Ok(());
+        }
+        validate(&self.target, &self.bag)?;
+        search(&self.target, self.bag.clone(), self.pinned.clone(), when_solved);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::util::target::rectangle;
+    use super::super::piece::Template;
+    use super::super::verification::verify;
+    use super::*;
+
+    fn bag() -> Bag<(i8, i8)> {
+        Bag::new(vec!(
+            (1, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(1, 1)))),
+            (3, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))),
+        ))
+    }
+
+    #[test]
+    fn pinned_pieces_should_be_part_of_every_solution() {
+        let target = rectangle(3, 2);
+        let setup = Setup::new(target.clone(), bag())
+            .pin(1, CubeSymmetry::E0123, Translation::from((0, 0)))
+            .unwrap();
+        let mut solutions = vec!();
+
+        setup.solve(&mut |solution| solutions.push(solution)).unwrap();
+
+        assert_eq!(setup.target().iter().count(), 4);
+        assert_eq!(setup.bag().volume(), 7);
+        assert_eq!(solutions.len(), 1);
+        for solution in &solutions {
+            assert_eq!(solution.pieces()[0], &Piece::new(vec!(Position::d2(0, 0), Position::d2(1, 0))));
+            assert_eq!(solution.placements()[0].template(), Some(1));
+            assert_eq!(verify(&target, &bag(), solution), Ok(()));
+        }
+    }
+
+    #[test]
+    fn blocked_cells_should_not_be_covered() {
+        let setup = Setup::new(rectangle(3, 2), bag()).block(vec!(Position::d2(0, 0), Position::d2(2, 1))).unwrap();
+        let mut solutions = vec!();
+
+        setup.solve(&mut |solution| solutions.push(solution)).unwrap();
+
+        assert_eq!(setup.blocked().len(), 2);
+        assert!(!solutions.is_empty());
+        assert!(solutions.iter().all(|solution| solution.piece_at(&Position::d2(0, 0)).is_none() && solution.piece_at(&Position::d2(2, 1)).is_none()));
+    }
+
+    #[test]
+    fn should_refuse_pins_that_do_not_fit() {
+        let setup = Setup::new(rectangle(3, 2), bag());
+
+        assert_eq!(setup.clone().pin(2, CubeSymmetry::E0123, Translation::from((0, 0))).err(), Some(PuzzleError::UnknownTemplate(2)));
+        assert_eq!(setup.clone().pin(0, CubeSymmetry::E0123, Translation::from((2, 0))).err(), Some(PuzzleError::Obstructed(Position::d2(3, 0))));
+        assert_eq!(setup.clone().pin(0, CubeSymmetry::E0132, Translation::from((0, 0))).err(), Some(PuzzleError::InadmissibleSymmetry(CubeSymmetry::E0132)));
+        let pinned = setup.clone().pin(0, CubeSymmetry::E0123, Translation::from((0, 0))).unwrap();
+        assert_eq!(pinned.clone().pin(0, CubeSymmetry::E0123, Translation::from((1, 0))).err(), Some(PuzzleError::TemplateUsedUp(0)));
+        assert_eq!(pinned.block(vec!(Position::d2(1, 1))).err(), Some(PuzzleError::Obstructed(Position::d2(1, 1))));
+    }
+
+    #[test]
+    fn should_refuse_pins_beyond_the_coordinates() {
+        let setup = Setup::new(rectangle(3, 2), bag());
+
+        assert_eq!(setup.clone().pin(1, CubeSymmetry::E0123, Translation::from((i8::MAX, 0))).err(), Some(PuzzleError::Obstructed(Position::d2(1, 0))));
+        assert_eq!(setup.pin(0, CubeSymmetry::E0123, Translation::from((0, i8::MAX))).err(), Some(PuzzleError::Obstructed(Position::d2(1, 1))));
+    }
+
+    #[test]
+    fn filled_targets_should_report_the_pinned_pieces() {
+        let setup = Setup::new(rectangle(2, 1), bag()).pin(1, CubeSymmetry::E0123, Translation::from((0, 0))).unwrap();
+        let mut solutions = vec!();
+
+        setup.solve(&mut |solution| solutions.push(solution)).unwrap();
+
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].len(), 1);
+    }
+}
@@ -0,0 +1,173 @@
+//! Geometric properties of collections of positions.
+
+use std::collections::BTreeSet;
+
+use super::piece::{Piece, Position, Template};
+use super::solver::Target;
+use super::vector::{Bounds, Neighbours};
+
+pub use super::vector::Connectivity;
+
+/// Analysis of the cells that make up an entity.
+pub trait Shape<T> where T: Clone + Ord + Neighbours<T> + Bounds<T> {
+    /// The `Position`s of the cells of this shape.
+    fn positions(&self) -> Vec<Position<T>>;
+
+    /// The number of cells.
+    fn volume(&self) -> usize {
+        self.positions().len()
+    }
+
+    /// The minimum and maximum corner of the smallest box containing the
+    /// cells, or `None` when there are no cells.
+    fn bounding_box(&self) -> Option<(T, T)> {
+        let positions = self.positions();
+        let first = positions.first()?.coordinates().clone();
+
+        Some(positions.iter().fold((first.clone(), first), |(lower, upper), position| {
+            (lower.minimum(position.coordinates()), upper.maximum(position.coordinates()))
+        }))
+    }
+
+    /// The number of faces of cells that do not touch an other cell.
+    ///
+    /// For planar shapes this is the perimeter.
+    fn surface_area(&self) -> usize {
+        let cells: BTreeSet<T> = self.positions().into_iter().map(|position| position.coordinates().clone()).collect();
+
+        cells.iter().map(|cell| T::faces() - cell.neighbours().iter().filter(|neighbour| cells.contains(neighbour)).count()).sum()
+    }
+
+    /// The connected components of the cells, each sorted.
+    fn components(&self, connectivity: Connectivity) -> Vec<Vec<Position<T>>> {
+        components(&self.positions(), connectivity)
+    }
+
+    /// Determine if the cells form a single connected component.
+    fn is_connected(&self, connectivity: Connectivity) -> bool {
+        self.components(connectivity).len() == 1
+    }
+
+    /// The enclosed holes, i.e. the groups of empty cells that can not be
+    /// reached from outside the bounding box by moving through faces.
+    ///
+    /// Empty cells on the faces of the bounding box can be reached from
+    /// outside, so are the groups that contain them.
+    fn holes(&self) -> Vec<Vec<Position<T>>> {
+        match self.bounding_box() {
+            None => vec!(),
+            Some((lower, upper)) => {
+                let cells: BTreeSet<T> = self.positions().into_iter().map(|position| position.coordinates().clone()).collect();
+                let empty: Vec<Position<T>> = lower
+                    .span(&upper)
+                    .into_iter()
+                    .filter(|cell| !cells.contains(cell))
+                    .map(Position::from)
+                    .collect();
+
+                components(&empty, Connectivity::Face)
+                    .into_iter()
+                    .filter(|component| component.iter().all(|position| !position.coordinates().on_boundary(&lower, &upper)))
+                    .collect()
+            },
+        }
+    }
+}
+
+impl<T> Shape<T> for Template<T> where T: Clone + Ord + Neighbours<T> + Bounds<T> {
+    fn positions(&self) -> Vec<Position<T>> {
+        self.iter().collect()
+    }
+}
+
+impl<T> Shape<T> for Piece<T> where T: Clone + Ord + Neighbours<T> + Bounds<T> {
+    fn positions(&self) -> Vec<Position<T>> {
+        self.iter().collect()
+    }
+}
+
+impl<T> Shape<T> for Target<T> where T: Clone + Ord + Neighbours<T> + Bounds<T> {
+    fn positions(&self) -> Vec<Position<T>> {
+        self.iter().collect()
+    }
+}
+
+/// Group `positions` in components of cells that touch with the `connectivity`.
+pub(crate) fn components<T>(positions: &[Position<T>], connectivity: Connectivity) -> Vec<Vec<Position<T>>> where T: Clone + Ord + Neighbours<T> {
+    let mut unvisited: BTreeSet<T> = positions.iter().map(|position| position.coordinates().clone()).collect();
+    let mut result = vec!();
+    while let Some(start) = unvisited.iter().next().cloned() {
+        unvisited.remove(&start);
+        let mut component = vec!(start);
+        let mut index = 0;
+        while index < component.len() {
+            for neighbour in component[index].adjacent(&connectivity) {
+                if unvisited.remove(&neighbour) {
+                    component.push(neighbour);
+                }
+            }
+            index += 1;
+        }
+        component.sort();
+        result.push(component.into_iter().map(Position::from).collect());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::util::target::{brick_with_holes, from_art};
+    use super::*;
+
+    #[test]
+    fn should_determine_components_by_connectivity() {
+        let target = from_art("#.\n.#\n");
+
+        assert_eq!(target.components(Connectivity::Face).len(), 2);
+        assert!(!target.is_connected(Connectivity::Edge));
+        assert!(target.is_connected(Connectivity::Vertex));
+    }
+
+    #[test]
+    fn should_distinguish_edge_and_vertex_connectivity_in_space() {
+        let edge = Template::new(vec!(Position::new(0, 0, 0), Position::new(1, 1, 0)));
+        let vertex = Template::new(vec!(Position::new(0, 0, 0), Position::new(1, 1, 1)));
+
+        assert!(edge.is_connected(Connectivity::Edge));
+        assert!(!vertex.is_connected(Connectivity::Edge));
+        assert!(vertex.is_connected(Connectivity::Vertex));
+    }
+
+    #[test]
+    fn should_measure_a_shape() {
+        let target = from_art("###\n#.#\n###\n");
+
+        assert_eq!(target.volume(), 8);
+        assert_eq!(target.bounding_box(), Some(((0, 0), (2, 2))));
+        assert_eq!(target.surface_area(), 16);
+        assert_eq!(target.holes(), vec!(vec!(Position::d2(1, 1))));
+    }
+
+    #[test]
+    fn should_find_enclosed_holes_in_space() {
+        let target = brick_with_holes(3, 3, 3, &[(1, 1, 1), (0, 0, 0)]);
+
+        assert_eq!(target.holes(), vec!(vec!(Position::new(1, 1, 1))));
+        assert_eq!(target.surface_area(), 54 + 6);
+    }
+
+    #[test]
+    fn should_analyse_shapes_at_the_edge_of_the_coordinates() {
+        let (low, high) = (i8::MAX - 2, i8::MAX);
+        let ring: Vec<Position<(i8, i8)>> = (low..=high)
+            .flat_map(|x| (low..=high).map(move |y| (x, y)))
+            .filter(|&cell| cell != (low + 1, low + 1))
+            .map(Position::from)
+            .collect();
+        let target = Target::new(ring);
+
+        assert_eq!(target.holes(), vec!(vec!(Position::d2(low + 1, low + 1))));
+        assert_eq!(target.surface_area(), 16);
+        assert_eq!(Target::new(vec!(Position::d2(i8::MIN, 0), Position::d2(i8::MAX, 0))).components(Connectivity::Vertex).len(), 2);
+    }
+}
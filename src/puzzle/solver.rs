@@ -1,12 +1,15 @@
 //! Solver for packing problems.
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Error};
 
-use super::vector::{VectorAdd, VectorDifference};
-use super::piece::{MinimumPosition, Position, Positionable, Translatable, Transformable, Normalizable, Piece};
+use super::validation::{check_distinct, PuzzleError};
+use super::vector::{Neighbours, VectorAdd, VectorDifference};
+use super::piece::{CubeSymmetry, MinimumPosition, Position, Positionable, PositionIterator, Template, Translatable, Translation, Transformable, Normalizable, Piece};
 use super::pieces::Bag;
 
 /// Region to be packed.
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 pub struct Target<T> {
     collection: Vec<Position<T>>,
 }
@@ -17,6 +20,19 @@ impl<T> Target<T> where T: PartialOrd + Ord + PartialEq + Eq + Clone {
         Target { collection }
     }
 
+    /// Create a new `Target` from a collection of `Position`s, provided they
+    /// are distinct and there is at least one.
+    pub fn try_new(collection: Vec<Position<T>>) -> Result<Target<T>, PuzzleError<T>> {
+        check_distinct(&collection)?;
+
+        Ok(Target::new(collection))
+    }
+
+    /// Check that this `Target` has at least one `Position` and no duplicates.
+    pub fn validate(&self) -> Result<(), PuzzleError<T>> {
+        check_distinct(&self.collection)
+    }
+
     /// Determine if there is nothing left to pack.
     pub fn is_packed(&self) -> bool {
         self.collection.is_empty()
@@ -38,6 +54,76 @@ impl<T> Target<T> where T: PartialOrd + Ord + PartialEq + Eq + Clone {
 
         Target::new(collection)
     }
+
+    /// Create an `Iterator` that iterates over all `Position`s still to be packed.
+    pub fn iter(&self) -> PositionIterator<T> {
+        PositionIterator::new(self.collection.to_vec())
+    }
+
+    /// Create a `Target` of the `Position`s that are in this `Target` or in `other`.
+    pub fn union(&self, other: &Target<T>) -> Target<T> {
+        let mut collection: Vec<Position<T>> = self.collection.to_vec();
+        for position in &other.collection {
+            if !collection.contains(position) {
+                collection.push(position.clone());
+            }
+        }
+
+        Target::new(collection)
+    }
+
+    /// Create a `Target` of the `Position`s that are in this `Target` and in `other`.
+    pub fn intersection(&self, other: &Target<T>) -> Target<T> {
+        self.filter(|position| other.collection.contains(position))
+    }
+
+    /// Create a `Target` of the `Position`s that are in this `Target` but not in `other`.
+    pub fn difference(&self, other: &Target<T>) -> Target<T> {
+        self.filter(|position| !other.collection.contains(position))
+    }
+
+    fn filter<P>(&self, predicate: P) -> Target<T> where P: Fn(&Position<T>) -> bool {
+        let collection: Vec<Position<T>> = self.collection
+            .iter()
+            .filter(|position| predicate(position))
+            .cloned()
+            .collect();
+
+        Target::new(collection)
+    }
+}
+
+impl<T> Target<T> where T: Transformable + PartialOrd + Ord {
+    /// Transform this `Target` by `symmetry`, provided the space admits it.
+    pub fn try_transform(&mut self, symmetry: &CubeSymmetry) -> Result<(), PuzzleError<T>> {
+        if !T::admits(symmetry) {
+            return Err(PuzzleError::InadmissibleSymmetry(symmetry.clone()));
+        }
+        self.transform(symmetry);
+
+        Ok(())
+    }
+}
+
+/// # Panics
+/// When the space does not admit the symmetry, because it would collapse
+/// `Position`s of the `Target`. See `Target::try_transform`.
+impl<T> Transformable for Target<T> where T: Transformable + PartialOrd + Ord {
+    fn transform(&mut self, symmetry: &CubeSymmetry) {
+        assert!(T::admits(symmetry), "symmetry is not admitted by the space of the target");
+        for position in &mut self.collection {
+            position.transform(symmetry);
+        }
+        self.collection.sort()
+    }
+}
+
+impl<T> Translatable<T> for Target<T> where T: VectorAdd<T> {
+    fn translate(&mut self, translation: &Translation<T>) {
+        for position in &mut self.collection {
+            position.translate(translation);
+        }
+    }
 }
 
 impl<T> MinimumPosition<T> for Target<T> where T: PartialOrd + Ord + Clone {
@@ -46,16 +132,68 @@ impl<T> MinimumPosition<T> for Target<T> where T: PartialOrd + Ord + Clone {
     }
 }
 
+/// How a `Piece` is placed in a `Solution`.
+///
+/// For pieces placed by the solver, the `Piece` is obtained by transforming
+/// the `Template` at index `template` of the `Bag` by `symmetry`, and then
+/// moving it by `translation`. Pieces that were only recorded have neither.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "T: serde::Deserialize<'de> + Ord + Clone")))]
+pub struct Placement<T> {
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    template: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    symmetry: Option<CubeSymmetry>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    translation: Option<Translation<T>>,
+    piece: Piece<T>,
+}
+
+impl<T> Placement<T> {
+    /// Create a `Placement` of the `Template` with index `template`, that results in `piece`.
+    pub fn new(template: usize, symmetry: CubeSymmetry, translation: Translation<T>, piece: Piece<T>) -> Placement<T> {
+        Placement { template: Some(template), symmetry: Some(symmetry), translation: Some(translation), piece }
+    }
+
+    /// Create a `Placement` of a `Piece` that does not refer to a `Template`.
+    pub fn recorded(piece: Piece<T>) -> Placement<T> {
+        Placement { template: None, symmetry: None, translation: None, piece }
+    }
+
+    /// The index of the `Template` in the `Bag`, if known.
+    pub fn template(&self) -> Option<usize> {
+        self.template
+    }
+
+    /// The `CubeSymmetry` that orients the `Template`, if known.
+    pub fn symmetry(&self) -> Option<&CubeSymmetry> {
+        self.symmetry.as_ref()
+    }
+
+    /// The `Translation` that moves the oriented `Template` in place, if known.
+    pub fn translation(&self) -> Option<&Translation<T>> {
+        self.translation.as_ref()
+    }
+
+    /// The placed `Piece`.
+    pub fn piece(&self) -> &Piece<T> {
+        &self.piece
+    }
+}
+
 /// (Partial) solution of a packing problem. Piece at their correct location are listed.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "T: serde::Deserialize<'de> + Ord + Clone")))]
 pub struct Solution<T> {
-    pieces: Vec<Piece<T>>
+    placements: Vec<Placement<T>>,
 }
 
 impl<T> Solution<T> where T : Clone {
     /// Empty solution. Serves as a starting point for the `solve` method.
     pub fn empty() -> Solution<T> {
-        Solution { pieces: vec!() }
+        Solution { placements: vec!() }
     }
 
     /// Record a `Piece` as part of the `Solution`.
@@ -63,17 +201,78 @@ impl<T> Solution<T> where T : Clone {
     /// Returns a new `Solutions` with the `Piece` added. *Note* the caller is
     /// responsible for checking if the `Piece` actually fits in the `Target`.
     pub fn record(&self, piece: &Piece<T>) -> Solution<T> {
-        let mut pieces: Vec<Piece<T>> = self.pieces.to_vec();
-        pieces.push(piece.clone());
+        self.place(&Placement::recorded(piece.clone()))
+    }
+
+    /// Record a `Placement` as part of the `Solution`.
+    ///
+    /// Returns a new `Solution` with the `Placement` added. *Note* the caller
+    /// is responsible for checking if the `Piece` actually fits in the `Target`.
+    pub fn place(&self, placement: &Placement<T>) -> Solution<T> {
+        let mut placements: Vec<Placement<T>> = self.placements.to_vec();
+        placements.push(placement.clone());
+
+        Solution { placements }
+    }
+
+    /// The `Placement`s of the `Piece`s, in the order they were recorded.
+    pub fn placements(&self) -> &[Placement<T>] {
+        &self.placements
+    }
 
-        Solution { pieces }
+    /// The `Piece`s of this `Solution`, in the order they were recorded.
+    pub fn pieces(&self) -> Vec<&Piece<T>> {
+        self.iter().collect()
+    }
+
+    /// Create an `Iterator` over the `Piece`s of this `Solution`.
+    pub fn iter(&self) -> impl Iterator<Item = &Piece<T>> {
+        self.placements.iter().map(|placement| &placement.piece)
+    }
+
+    /// The number of `Piece`s in this `Solution`.
+    pub fn len(&self) -> usize {
+        self.placements.len()
+    }
+
+    /// Determine if no `Piece` has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.placements.is_empty()
+    }
+}
+
+impl<T> Solution<T> where T: Clone + PartialOrd + Ord {
+    /// The names of the `Piece`s, in the order they were recorded.
+    pub fn names(&self) -> Vec<Option<&str>> {
+        self.iter().map(|piece| piece.name()).collect()
+    }
+
+    /// The index of the `Piece` that covers `position`, if any.
+    pub fn index_at(&self, position: &Position<T>) -> Option<usize> {
+        self.iter().position(|piece| piece.contains(position))
+    }
+
+    /// The `Piece` that covers `position`, if any.
+    pub fn piece_at(&self, position: &Position<T>) -> Option<&Piece<T>> {
+        self.index_at(position).map(|index| &self.placements[index].piece)
+    }
+
+    /// Map every covered `Position` to the index of the `Piece` covering it.
+    pub fn cells(&self) -> BTreeMap<Position<T>, usize> {
+        let mut cells = BTreeMap::new();
+        for (index, piece) in self.iter().enumerate() {
+            for position in piece.iter() {
+                cells.insert(position, index);
+            }
+        }
+        cells
     }
 }
 
 impl Display for Solution<(i8, i8, i8)> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "<")?;
-        for piece in &self.pieces {
+        for piece in self.iter() {
             write!(f, "{}", piece)?;
         }
         write!(f, ">")
@@ -83,7 +282,7 @@ impl Display for Solution<(i8, i8, i8)> {
 impl Display for Solution<(i8, i8)> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "<")?;
-        for piece in &self.pieces {
+        for piece in self.iter() {
             write!(f, "{}", piece)?;
         }
         write!(f, ">")
@@ -91,33 +290,201 @@ impl Display for Solution<(i8, i8)> {
 }
 
 
+/// Check that the `Target` and the `Bag` form a consistent packing problem.
+///
+/// The `Target` and all `Template`s must be valid, and the `Bag` must hold
+/// enough pieces to fill the `Target`. A `Bag` may hold more pieces than
+/// needed, solutions then leave some of them unused.
+pub fn validate<T>(target: &Target<T>, bag: &Bag<T>) -> Result<(), PuzzleError<T>> where T: Clone + Ord + Neighbours<T> {
+    target.validate()?;
+    bag.validate()?;
+    let volume = bag.volume();
+    if volume < target.collection.len() {
+        return Err(PuzzleError::VolumeMismatch { target: target.collection.len(), bag: volume });
+    }
+    Ok(())
+}
+
 /// Attempt to pack all the `Piece`s in the `Bag` into the `Target` region. When
 /// a solution is found, the `when_solved` callback is called with that solution.
-pub fn solve<F, T>(target: &Target<T>, bag: Bag<T>, when_solved: &mut F) where F: (FnMut(Solution<T>)) + Sized, T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
+///
+/// Inconsistent problems, as determined by `validate`, are refused. This
+/// includes an empty `Target`, which is refused with `PuzzleError::Empty`.
+pub fn solve<F, T>(target: &Target<T>, bag: Bag<T>, when_solved: &mut F) -> Result<(), PuzzleError<T>> where F: (FnMut(Solution<T>)) + Sized, T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
     let partial_solution: Solution<T> = Solution::empty();
     solve_with(target, bag, partial_solution, when_solved)
 }
 
 
 /// Variant of the `solve` method that allows for a different starting point.
-pub fn solve_with<F, T>(target: &Target<T>, bag: Bag<T>, partial_solution: Solution<T>, when_solved: &mut F) where F: (FnMut(Solution<T>)) + Sized, T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
-    if target.is_packed() {
-        when_solved(partial_solution)
-    } else {
-        let open_position = target.minimum_position().unwrap();
-        for (template, rest_of_bag) in bag {
-            for mut piece in template {
-                let block = piece.minimum_position().unwrap();
-                let translation = block.to(&open_position);
+///
+/// The pieces of the `partial_solution` are only reported, they are not
+/// removed from the `Target` or the `Bag`. Use a `Setup` to pin pieces in
+/// place before solving.
+pub fn solve_with<F, T>(target: &Target<T>, bag: Bag<T>, partial_solution: Solution<T>, when_solved: &mut F) -> Result<(), PuzzleError<T>> where F: (FnMut(Solution<T>)) + Sized, T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    validate(target, &bag)?;
+    search(target, bag, partial_solution, &mut |solution| { when_solved(solution); true });
+    Ok(())
+}
+
+/// Variant of the `solve` method that stops searching as soon as the
+/// `when_solved` callback returns `false`.
+pub fn solve_while<F, T>(target: &Target<T>, bag: Bag<T>, when_solved: &mut F) -> Result<(), PuzzleError<T>> where F: (FnMut(Solution<T>) -> bool) + Sized, T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    validate(target, &bag)?;
+    search(target, bag, Solution::empty(), when_solved);
+    Ok(())
+}
+
+/// Determine if the `Target` can be tiled with copies of the `templates`,
+/// using as many copies of each as needed. Returns a tiling if there is one.
+pub fn tiles<T>(target: &Target<T>, templates: &[Template<T>]) -> Result<Option<Solution<T>>, PuzzleError<T>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    let mut tiling = None;
+    solve_while(target, Bag::unlimited(templates.to_vec()), &mut |solution| {
+        tiling = Some(solution);
+        false
+    })?;
+    Ok(tiling)
+}
+
+/// An orientation of a `Template`, with its minimum `Position` at the reference `Position`.
+pub(crate) struct Orientation<T> {
+    pub(crate) symmetry: CubeSymmetry,
+    /// The minimum `Position` of the `Template` after applying the `symmetry`.
+    pub(crate) anchor: Position<T>,
+    pub(crate) piece: Piece<T>,
+}
+
+/// The orientations of every `Template` in a `Bag`, by index.
+pub(crate) struct Orientations<T> {
+    templates: Vec<Vec<Orientation<T>>>,
+    /// For every `Template`, the index of the first congruent `Template` with the same name.
+    congruence: Vec<usize>,
+}
+
+impl<T> Orientations<T> {
+    /// Determine if a `Template` is superseded by an earlier congruent
+    /// `Template` with pieces left. Both offer the same moves, so trying
+    /// either one is enough.
+    fn superseded(&self, index: usize, counts: &[usize]) -> bool {
+        (0..index).any(|other| self.congruence[other] == self.congruence[index] && counts[other] > 0)
+    }
+}
+
+/// Determine the orientations of every `Template` in the `Bag`, by index.
+pub(crate) fn orientations<T>(bag: &Bag<T>) -> Orientations<T> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
+    let templates = bag.iter()
+        .map(|(_, template)| {
+            template.orientations()
+                .into_iter()
+                .map(|(symmetry, piece)| {
+                    let mut oriented = Piece::from(template.clone());
+                    oriented.transform(&symmetry);
+                    let anchor = oriented.minimum_position().unwrap();
+
+                    Orientation { symmetry, anchor, piece }
+                })
+                .collect()
+        })
+        .collect();
+
+    Orientations { templates, congruence: bag.congruence() }
+}
+
+/// Search for solutions. Returns whether the search should continue.
+pub(crate) fn search<F, T>(target: &Target<T>, bag: Bag<T>, partial_solution: Solution<T>, when_solved: &mut F) -> bool where F: (FnMut(Solution<T>) -> bool) + Sized, T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
+    let orientations = orientations(&bag);
+    let mut counts: Vec<usize> = bag.iter().map(|(count, _)| *count).collect();
+
+    explore(target, &orientations, &mut counts, partial_solution, &mut Solutions { when_solved })
+}
+
+/// How the search continues at a partial solution.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Step {
+    /// Try every move.
+    Descend,
+    /// Try every move, and then leave the minimum open `Position` empty.
+    DescendAndSkip,
+    /// Do not extend this partial solution.
+    Prune,
+    /// Stop the whole search.
+    Stop,
+}
+
+/// Steers the search of `explore`.
+pub(crate) trait Visitor<T> {
+    /// Decide how to continue at a partial solution, given the remaining
+    /// `Target`, the number of pieces left of each `Template` and the number
+    /// of moves that cover the minimum open `Position`.
+    fn visit(&mut self, target: &Target<T>, partial_solution: &Solution<T>, counts: &[usize], moves: usize) -> Step;
+}
+
+/// Reports the solutions to a callback, until it returns `false`.
+struct Solutions<'a, F> {
+    when_solved: &'a mut F,
+}
+
+impl<'a, F, T> Visitor<T> for Solutions<'a, F> where F: FnMut(Solution<T>) -> bool, T: Clone + PartialOrd + Ord {
+    fn visit(&mut self, target: &Target<T>, partial_solution: &Solution<T>, _: &[usize], _: usize) -> Step {
+        if !target.is_packed() {
+            Step::Descend
+        } else if (self.when_solved)(partial_solution.clone()) {
+            Step::Prune
+        } else {
+            Step::Stop
+        }
+    }
+}
+
+/// Cover the minimum open `Position` of the `Target` with every available
+/// orientation of every `Template` that still has pieces left, as steered by
+/// the `visitor`. Returns whether the search was completed, i.e. not stopped.
+///
+/// Of congruent `Template`s with the same name only the first one with pieces
+/// left is placed, so that solutions are not found once for every way to swap
+/// their pieces.
+pub(crate) fn explore<V, T>(target: &Target<T>, orientations: &Orientations<T>, counts: &mut Vec<usize>, partial_solution: Solution<T>, visitor: &mut V) -> bool where V: Visitor<T>, T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
+    let open_position = target.minimum_position();
+    let mut moves: Vec<Placement<T>> = vec!();
+    if let Some(open_position) = &open_position {
+        for (index, candidates) in orientations.templates.iter().enumerate() {
+            if counts[index] == 0 || orientations.superseded(index, counts) {
+                continue;
+            }
+            for orientation in candidates {
+                let mut piece = orientation.piece.clone();
+                let translation = piece.minimum_position().unwrap().to(open_position);
                 piece.translate(&translation);
                 if target.fits(&piece) {
-                    let remaining_target = target.place(&piece);
-                    let candidate_solution = partial_solution.record(&piece);
-                    solve_with(&remaining_target, rest_of_bag.clone(), candidate_solution, when_solved)
+                    moves.push(Placement::new(index, orientation.symmetry.clone(), orientation.anchor.to(open_position), piece));
                 }
             }
         }
     }
+
+    let step = visitor.visit(target, &partial_solution, counts, moves.len());
+    match step {
+        Step::Stop => return false,
+        Step::Prune => return true,
+        Step::Descend | Step::DescendAndSkip => {},
+    }
+    for placement in moves {
+        let index = placement.template().unwrap();
+        let remaining_target = target.place(placement.piece());
+        counts[index] -= 1;
+        let completed = explore(&remaining_target, orientations, counts, partial_solution.place(&placement), visitor);
+        counts[index] += 1;
+        if !completed {
+            return false;
+        }
+    }
+    match (step, open_position) {
+        (Step::DescendAndSkip, Some(open_position)) => {
+            let remaining_target = target.place(&Piece::new(vec!(open_position)));
+            explore(&remaining_target, orientations, counts, partial_solution, visitor)
+        },
+        _ => true,
+    }
 }
 
 #[cfg(test)]
@@ -173,10 +540,25 @@ mod tests {
         ));
 
         let mut solutions: Vec<Solution<(i8, i8, i8)>> = vec!();
-        solve(&target, bag, &mut |solution|{ solutions.push(solution)});
+        solve(&target, bag, &mut |solution|{ solutions.push(solution)}).unwrap();
         assert_eq!(solutions.len(), 4);
     }
 
+    #[test]
+    fn solve_while_should_stop_when_asked() {
+        let target = Target::new(vec!(
+            Position::d2(0, 0), Position::d2(1, 0),
+            Position::d2(0, 1), Position::d2(1, 1),
+        ));
+        let bag = Bag::new(vec!(
+            (2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))),
+        ));
+
+        let mut count = 0;
+        solve_while(&target, bag, &mut |_| { count += 1; false }).unwrap();
+        assert_eq!(count, 1);
+    }
+
     #[test]
     fn solutions_should_display_nicely() {
         let solution =
@@ -201,4 +583,176 @@ mod tests {
 
         assert_eq!(output, String::from("<[(0, 0, 0)(0, 0, 1)(0, 1, 0)(1, 0, 0)][(0, 1, 1)(1, 0, 1)(1, 1, 0)(1, 1, 1)]>"));
     }
+
+    #[test]
+    fn solve_should_refuse_inconsistent_problems() {
+        let target = Target::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0)));
+        let bag = Bag::new(vec!((1, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0))))));
+
+        let result = solve(&target, bag, &mut |_| panic!("no solution expected"));
+
+        assert_eq!(result, Err(PuzzleError::VolumeMismatch { target: 3, bag: 2 }));
+        assert_eq!(Target::try_new(vec!(Position::d2(0, 0), Position::d2(0, 0))), Err(PuzzleError::DuplicatePosition(Position::d2(0, 0))));
+    }
+
+    #[test]
+    fn tiles_should_use_as_many_copies_as_needed() {
+        let trominoes = vec!(Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(1, 1))));
+        let rectangle = |width: i8, height: i8| {
+            Target::new((0..width).flat_map(|x| (0..height).map(move |y| Position::d2(x, y))).collect())
+        };
+
+        let tiling = tiles(&rectangle(3, 4), &trominoes).unwrap();
+
+        assert_eq!(tiling.map(|solution| solution.len()), Some(4));
+        assert!(tiles(&rectangle(3, 3), &trominoes).unwrap().is_none());
+    }
+
+    #[test]
+    fn bags_should_hold_more_than_255_pieces() {
+        let target = Target::new((0..20).flat_map(|x| (0..20).map(move |y| Position::d2(x, y))).collect());
+        let bag = Bag::new(vec!((300, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0))))));
+        let mut pieces = 0;
+
+        solve_while(&target, bag, &mut |solution| { pieces = solution.len(); false }).unwrap();
+
+        assert_eq!(pieces, 200);
+    }
+
+    #[test]
+    fn solutions_should_record_placements() {
+        let target = Target::new(vec!(Position::d2(0, 0), Position::d2(0, 1)));
+        let bag = Bag::new(vec!(
+            (1, Template::new(vec!(Position::d2(0, 0))).with_name("unused")),
+            (1, Template::new(vec!(Position::d2(5, 5), Position::d2(6, 5))).with_name("I")),
+        ));
+        let mut solutions: Vec<Solution<(i8, i8)>> = vec!();
+
+        solve(&target, bag, &mut |solution| solutions.push(solution)).unwrap();
+
+        let placements: Vec<&Placement<(i8, i8)>> = solutions.iter().flat_map(|solution| solution.placements()).collect();
+        assert_eq!(placements.len(), 1);
+        let placement = placements[0];
+        assert_eq!(placement.template(), Some(1));
+        let mut piece = Piece::from(Template::new(vec!(Position::d2(5, 5), Position::d2(6, 5))).with_name("I"));
+        piece.transform(placement.symmetry().unwrap());
+        piece.translate(placement.translation().unwrap());
+        assert_eq!(&piece, placement.piece());
+        assert_eq!(placement.piece(), &Piece::named(vec!(Position::d2(0, 0), Position::d2(0, 1)), "I"));
+    }
+
+    #[test]
+    fn congruent_templates_should_not_repeat_solutions() {
+        let target = Target::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(0, 1), Position::d2(1, 1)));
+        let bag = Bag::new(vec!(
+            (1, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0))).with_name("I")),
+            (1, Template::new(vec!(Position::d2(3, 3), Position::d2(3, 4))).with_name("I")),
+        ));
+        let mut solutions: Vec<Solution<(i8, i8)>> = vec!();
+
+        solve(&target, bag.clone(), &mut |solution| solutions.push(solution)).unwrap();
+
+        assert_eq!(solutions.len(), 2);
+        for solution in &solutions {
+            let mut indices: Vec<usize> = solution.placements().iter().filter_map(|placement| placement.template()).collect();
+            indices.sort();
+            assert_eq!(indices, vec!(0, 1));
+            for placement in solution.placements() {
+                let (_, template) = bag.iter().nth(placement.template().unwrap()).unwrap();
+                let mut piece = Piece::from(template.clone());
+                piece.transform(placement.symmetry().unwrap());
+                piece.translate(placement.translation().unwrap());
+                assert_eq!(&piece, placement.piece());
+            }
+        }
+    }
+
+    #[test]
+    fn solutions_should_be_inspectable() {
+        let solution = Solution::empty()
+            .record(&Piece::named(vec!(Position::d2(0, 0), Position::d2(1, 0)), "I"))
+            .record(&Piece::new(vec!(Position::d2(0, 1))));
+
+        assert_eq!(solution.len(), 2);
+        assert_eq!(solution.names(), vec!(Some("I"), None));
+        assert_eq!(solution.index_at(&Position::d2(0, 1)), Some(1));
+        assert_eq!(solution.piece_at(&Position::d2(1, 0)).and_then(|piece| piece.name()), Some("I"));
+        assert!(solution.piece_at(&Position::d2(1, 1)).is_none());
+        assert_eq!(solution.cells().into_iter().collect::<Vec<(Position<(i8, i8)>, usize)>>(), vec!(
+            (Position::d2(0, 0), 0),
+            (Position::d2(0, 1), 1),
+            (Position::d2(1, 0), 0),
+        ));
+    }
+
+    #[test]
+    fn recorded_and_placed_pieces_should_share_indices() {
+        let placed = Piece::named(vec!(Position::d2(1, 0)), "B");
+        let solution = Solution::empty()
+            .record(&Piece::named(vec!(Position::d2(0, 0)), "A"))
+            .place(&Placement::new(0, CubeSymmetry::E0123, Translation::from((1i8, 0i8)), placed.clone()));
+
+        assert_eq!(solution.pieces(), vec!(&Piece::named(vec!(Position::d2(0, 0)), "A"), &placed));
+        assert_eq!(solution.placements()[0].template(), None);
+        assert_eq!(solution.placements()[1].template(), Some(0));
+        assert_eq!(solution.index_at(&Position::d2(1, 0)), Some(1));
+    }
+
+    #[test]
+    fn targets_should_combine_as_sets() {
+        let a = Target::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+        let b = Target::new(vec!(Position::d2(1, 0), Position::d2(2, 0)));
+
+        assert_eq!(a.union(&b), Target::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0))));
+        assert_eq!(a.intersection(&b), Target::new(vec!(Position::d2(1, 0))));
+        assert_eq!(a.difference(&b), Target::new(vec!(Position::d2(0, 0))));
+    }
+
+    #[test]
+    fn targets_should_move() {
+        let mut target = Target::new(vec!(Position::new(0, 0, 0), Position::new(1, 0, 0)));
+
+        target.translate(&Translation::new(0, 1, 0));
+        assert_eq!(target, Target::new(vec!(Position::new(0, 1, 0), Position::new(1, 1, 0))));
+
+        target.transform(&CubeSymmetry::E1230);
+        assert_eq!(target, Target::new(vec!(Position::new(-1, 0, 0), Position::new(-1, 1, 0))));
+    }
+
+    #[test]
+    fn planar_targets_should_refuse_symmetries_outside_the_plane() {
+        let mut target = Target::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(0, 1)));
+
+        assert_eq!(target.try_transform(&CubeSymmetry::E0132), Err(PuzzleError::InadmissibleSymmetry(CubeSymmetry::E0132)));
+        assert_eq!(target.iter().count(), 3);
+        assert_eq!(target.try_transform(&CubeSymmetry::E1230), Ok(()));
+        assert_eq!(target, Target::new(vec!(Position::d2(-1, 0), Position::d2(0, 0), Position::d2(0, 1))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn targets_should_serialize_as_list_of_positions() {
+        let target = Target::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+
+        let json = serde_json::to_string(&target).expect("to serialize target");
+        assert_eq!(json, "[[0,0],[1,0]]");
+
+        let copy: Target<(i8, i8)> = serde_json::from_str(&json).expect("to deserialize target");
+        assert_eq!(copy, target);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn solutions_should_serialize_with_piece_names() {
+        let solution =
+            Solution::empty()
+            .record(&Piece::named(vec!(Position::d2(1, 0), Position::d2(0, 0)), "I"))
+            .record(&Piece::new(vec!(Position::d2(0, 1))));
+
+        let json = serde_json::to_string(&solution).expect("to serialize solution");
+        assert_eq!(json, r#"{"placements":[{"piece":{"positions":[[0,0],[1,0]],"name":"I"}},{"piece":{"positions":[[0,1]]}}]}"#);
+
+        let copy: Solution<(i8, i8)> = serde_json::from_str(r#"{"placements":[{"piece":{"positions":[[1,0],[0,0]],"name":"I"}},{"piece":{"positions":[[0,1]]}}]}"#).expect("to deserialize solution");
+        assert_eq!(serde_json::to_string(&copy).expect("to serialize solution"), json);
+    }
 }
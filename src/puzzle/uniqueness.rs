@@ -0,0 +1,196 @@
+//! Determine if a packing problem has exactly one solution.
+//!
+//! Puzzle designers mostly want to know whether their puzzle has a unique
+//! solution. The search stops as soon as a second distinct solution is
+//! found, so the question is answered without counting all solutions.
+
+use super::piece::{CubeSymmetry, CubeSymmetryIterator, MinimumPosition, Normalizable, Position, Positionable, Transformable, Translatable, Translation};
+use super::pieces::Bag;
+use super::setup::Setup;
+use super::solver::{solve_while, Solution, Target};
+use super::validation::PuzzleError;
+use super::vector::{Neighbours, VectorAdd, VectorDifference};
+
+/// How many distinct solutions a packing problem has.
+#[derive(Debug, Clone)]
+pub enum Uniqueness<T> {
+    /// There is no solution.
+    Unsolvable,
+    /// There is exactly one solution.
+    Unique(Solution<T>),
+    /// There are at least two solutions, witnessed by two distinct ones.
+    Multiple(Solution<T>, Solution<T>),
+}
+
+impl<T> Uniqueness<T> {
+    /// Determine if there is exactly one solution.
+    pub fn is_unique(&self) -> bool {
+        matches!(self, Uniqueness::Unique(_))
+    }
+}
+
+/// Determine if packing the `Target` with pieces from the `Bag` has no, one
+/// or multiple solutions.
+///
+/// Solutions that are symmetric images of each other count as distinct.
+pub fn uniqueness<T>(target: &Target<T>, bag: &Bag<T>) -> Result<Uniqueness<T>, PuzzleError<T>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    classify(&[], |when_solved| solve_while(target, bag.clone(), &mut |solution| when_solved(solution)))
+}
+
+/// Variant of `uniqueness` that considers solutions the same when a
+/// symmetry of the `Target` maps one onto the other.
+pub fn uniqueness_modulo_symmetry<T>(target: &Target<T>, bag: &Bag<T>) -> Result<Uniqueness<T>, PuzzleError<T>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    classify(&symmetries(target), |when_solved| solve_while(target, bag.clone(), &mut |solution| when_solved(solution)))
+}
+
+/// Variant of `uniqueness` for a `Setup`, i.e. with pieces pinned in place.
+///
+/// The solutions include the pinned pieces.
+pub fn uniqueness_of<T>(setup: &Setup<T>) -> Result<Uniqueness<T>, PuzzleError<T>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    classify(&[], |when_solved| setup.solve_while(&mut |solution| when_solved(solution)))
+}
+
+/// The symmetries that map the `Target` onto itself, with the `Translation`
+/// that moves its image back in place.
+pub fn symmetries<T>(target: &Target<T>) -> Vec<(CubeSymmetry, Translation<T>)> where T: Clone + PartialOrd + Ord + Transformable + VectorDifference<T> + VectorAdd<T> {
+    let minimum = match target.minimum_position() {
+        Some(minimum) => minimum,
+        None => return vec!(),
+    };
+    let cells = sorted(target.iter().collect());
+
+    CubeSymmetryIterator::new()
+        .filter(T::admits)
+        .filter_map(|symmetry| {
+            let mut image = target.clone();
+            image.transform(&symmetry);
+            let translation = image.minimum_position().unwrap().to(&minimum);
+            image.translate(&translation);
+
+            if sorted(image.iter().collect()) == cells { Some((symmetry, translation)) } else { None }
+        })
+        .collect()
+}
+
+/// Search until a second solution is found that is neither the first, nor an
+/// image of the first under one of the `symmetries`.
+fn classify<T, S>(symmetries: &[(CubeSymmetry, Translation<T>)], solve: S) -> Result<Uniqueness<T>, PuzzleError<T>> where T: Clone + Ord + Transformable + VectorAdd<T>, S: FnOnce(&mut dyn FnMut(Solution<T>) -> bool) -> Result<(), PuzzleError<T>> {
+    let mut first: Option<(Solution<T>, Vec<Layout<T>>)> = None;
+    let mut second: Option<Solution<T>> = None;
+
+    solve(&mut |solution| {
+        match &first {
+            None => {
+                let original = layout(&solution);
+                let mut images: Vec<Layout<T>> = symmetries.iter().map(|(symmetry, translation)| image(&original, symmetry, translation)).collect();
+                images.push(original);
+                first = Some((solution, images));
+                true
+            },
+            Some((_, images)) => {
+                if images.contains(&layout(&solution)) {
+                    true
+                } else {
+                    second = Some(solution);
+                    false
+                }
+            },
+        }
+    })?;
+
+    Ok(match (first, second) {
+        (None, _) => Uniqueness::Unsolvable,
+        (Some((solution, _)), None) => Uniqueness::Unique(solution),
+        (Some((solution, _)), Some(other)) => Uniqueness::Multiple(solution, other),
+    })
+}
+
+/// The placed `Template`s of a `Solution` with the `Position`s they cover.
+type Layout<T> = Vec<(Option<usize>, Vec<Position<T>>)>;
+
+/// The `Layout` of a `Solution`.
+fn layout<T>(solution: &Solution<T>) -> Layout<T> where T: Clone + Ord {
+    let layout = solution.placements()
+        .iter()
+        .map(|placement| (placement.template(), sorted(placement.piece().iter().collect())))
+        .collect();
+
+    sorted(layout)
+}
+
+/// The image of a `Layout` under a symmetry.
+fn image<T>(layout: &Layout<T>, symmetry: &CubeSymmetry, translation: &Translation<T>) -> Layout<T> where T: Clone + Ord + Transformable + VectorAdd<T> {
+    let layout = layout
+        .iter()
+        .map(|(template, positions)| {
+            let positions = positions
+                .iter()
+                .map(|position| {
+                    let mut position = position.clone();
+                    position.transform(symmetry);
+                    position.translate(translation);
+                    position
+                })
+                .collect();
+
+            (*template, sorted(positions))
+        })
+        .collect();
+
+    sorted(layout)
+}
+
+fn sorted<S>(mut items: Vec<S>) -> Vec<S> where S: Ord {
+    items.sort();
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::util::target::rectangle;
+    use super::super::piece::Template;
+    use super::*;
+
+    fn dominoes(count: usize) -> Bag<(i8, i8)> {
+        Bag::new(vec!((count, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0))))))
+    }
+
+    #[test]
+    fn should_find_unique_solutions() {
+        assert!(uniqueness(&rectangle(2, 1), &dominoes(1)).unwrap().is_unique());
+        assert!(!uniqueness(&rectangle(2, 2), &dominoes(2)).unwrap().is_unique());
+    }
+
+    #[test]
+    fn should_report_two_distinct_witnesses() {
+        match uniqueness(&rectangle(3, 2), &dominoes(3)).unwrap() {
+            Uniqueness::Multiple(first, second) => assert_ne!(first.cells(), second.cells()),
+            other => panic!("expected multiple solutions, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_report_unsolvable_problems() {
+        let bag = Bag::new(vec!((2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(1, 1))))));
+
+        assert!(matches!(uniqueness(&rectangle(2, 2), &bag).unwrap(), Uniqueness::Unsolvable));
+    }
+
+    #[test]
+    fn pinned_pieces_should_make_solutions_unique() {
+        let setup = Setup::new(rectangle(3, 2), dominoes(3)).pin(0, CubeSymmetry::E0123, Translation::from((0, 0))).unwrap();
+
+        match uniqueness_of(&setup).unwrap() {
+            Uniqueness::Unique(solution) => assert_eq!(solution.len(), 3),
+            other => panic!("expected a unique solution, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_identify_solutions_modulo_symmetry() {
+        assert_eq!(symmetries(&rectangle(2, 2)).len(), 8);
+        assert_eq!(symmetries(&rectangle(3, 2)).len(), 4);
+        assert!(uniqueness_modulo_symmetry(&rectangle(2, 2), &dominoes(2)).unwrap().is_unique());
+        assert!(!uniqueness_modulo_symmetry(&rectangle(3, 2), &dominoes(3)).unwrap().is_unique());
+    }
+}
@@ -0,0 +1,105 @@
+//! Validation of puzzle inputs.
+
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+
+use super::piece::{CubeSymmetry, Position};
+use super::shape::components;
+use super::vector::Connectivity;
+use super::vector::Neighbours;
+
+/// Problems with the input of a packing problem.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PuzzleError<T> {
+    /// A shape or target has no positions.
+    Empty,
+    /// A position occurs more than once.
+    DuplicatePosition(Position<T>),
+    /// The positions of a shape are not connected through faces.
+    Disconnected,
+    /// A template in a bag is invalid.
+    InvalidTemplate {
+        /// Index of the template in the bag.
+        index: usize,
+        /// What is wrong with the template.
+        error: Box<PuzzleError<T>>,
+    },
+    /// The pieces in the bag can not fill the target.
+    VolumeMismatch {
+        /// Number of positions in the target.
+        target: usize,
+        /// Number of positions of all the pieces in the bag.
+        bag: usize,
+    },
+    /// A pinned piece refers to a template that is not in the bag.
+    UnknownTemplate(usize),
+    /// A pinned piece refers to a template of which no pieces are left.
+    TemplateUsedUp(usize),
+    /// A pinned piece does not tell which template is placed, or how.
+    IncompletePlacement,
+    /// A pinned piece is transformed by a symmetry the space does not admit.
+    InadmissibleSymmetry(CubeSymmetry),
+    /// A pinned piece or blocked cell needs a position that is not open in
+    /// the target.
+    Obstructed(Position<T>),
+}
+
+impl<T> Display for PuzzleError<T> where T: Debug {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PuzzleError::Empty => write!(f, "no positions"),
+            PuzzleError::DuplicatePosition(position) => write!(f, "position {:?} occurs more than once", position.coordinates()),
+            PuzzleError::Disconnected => write!(f, "positions are not connected"),
+            PuzzleError::InvalidTemplate { index, error } => write!(f, "template {}: {}", index, error),
+            PuzzleError::VolumeMismatch { target, bag } => write!(f, "bag of volume {} can not fill target of volume {}", bag, target),
+            PuzzleError::UnknownTemplate(index) => write!(f, "there is no template {} in the bag", index),
+            PuzzleError::TemplateUsedUp(index) => write!(f, "no pieces of template {} are left", index),
+            PuzzleError::IncompletePlacement => write!(f, "placement does not describe how a template is placed"),
+            PuzzleError::InadmissibleSymmetry(symmetry) => write!(f, "symmetry {:?} is not admitted", symmetry),
+            PuzzleError::Obstructed(position) => write!(f, "position {:?} is not open in the target", position.coordinates()),
+        }
+    }
+}
+
+impl<T> Error for PuzzleError<T> where T: Debug {}
+
+/// Check that `positions` are not empty and contain no duplicates.
+pub(crate) fn check_distinct<T>(positions: &[Position<T>]) -> Result<(), PuzzleError<T>> where T: Clone + Ord {
+    if positions.is_empty() {
+        return Err(PuzzleError::Empty);
+    }
+    let mut sorted = positions.to_vec();
+    sorted.sort();
+    match sorted.windows(2).find(|pair| pair[0] == pair[1]) {
+        Some(pair) => Err(PuzzleError::DuplicatePosition(pair[0].clone())),
+        None => Ok(()),
+    }
+}
+
+/// Check that `positions` form a single shape, i.e. are distinct and connected through faces.
+pub(crate) fn check_shape<T>(positions: &[Position<T>]) -> Result<(), PuzzleError<T>> where T: Clone + Ord + Neighbours<T> {
+    check_distinct(positions)?;
+
+    if components(positions, Connectivity::Face).len() == 1 {
+        Ok(())
+    } else {
+        Err(PuzzleError::Disconnected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_accept_a_connected_shape() {
+        assert_eq!(check_shape(&[Position::d2(0, 0), Position::d2(0, 1), Position::d2(1, 1)]), Ok(()));
+    }
+
+    #[test]
+    fn should_report_problems_with_shapes() {
+        assert_eq!(check_shape::<(i8, i8)>(&[]), Err(PuzzleError::Empty));
+        assert_eq!(check_shape(&[Position::d2(1, 1), Position::d2(0, 0), Position::d2(1, 1)]), Err(PuzzleError::DuplicatePosition(Position::d2(1, 1))));
+        assert_eq!(check_shape(&[Position::new(0, 0, 0), Position::new(1, 1, 0)]), Err(PuzzleError::Disconnected));
+    }
+}
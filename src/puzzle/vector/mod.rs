@@ -3,6 +3,20 @@
 //! Mathematical vectors are things that will implement (a selection) of the
 //! triats in this module. Not necessarily a `Vec`.
 
+/// How cells need to touch to be connected.
+///
+/// For planar shapes squares that share an edge share a face as well, so
+/// `Edge` and `Face` coincide.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Connectivity {
+    /// Cells are connected when they share a face.
+    Face,
+    /// Cells are connected when they share at least an edge.
+    Edge,
+    /// Cells are connected when they share at least a vertex.
+    Vertex,
+}
+
 /// Determine the difference of two mathematical vectors.
 pub trait VectorDifference<T> {
     /// other - self
@@ -25,6 +39,9 @@ impl VectorDifference<(i8, i8, i8)> for (i8, i8, i8) {
 pub trait VectorAdd<T> {
     /// self += other
     fn add(&mut self, other: &T);
+
+    /// self + other, or `None` when the sum is beyond the range of the coordinates
+    fn checked_add(&self, other: &T) -> Option<T>;
 }
 
 impl VectorAdd<(i8, i8)> for (i8, i8) {
@@ -32,6 +49,10 @@ impl VectorAdd<(i8, i8)> for (i8, i8) {
         self.0 += other.0;
         self.1 += other.1;
     }
+
+    fn checked_add(&self, other: &(i8, i8)) -> Option<(i8, i8)> {
+        Some((self.0.checked_add(other.0)?, self.1.checked_add(other.1)?))
+    }
 }
 
 impl VectorAdd<(i8, i8, i8)> for (i8, i8, i8) {
@@ -40,6 +61,167 @@ impl VectorAdd<(i8, i8, i8)> for (i8, i8, i8) {
         self.1 += other.1;
         self.2 += other.2;
     }
+
+    fn checked_add(&self, other: &(i8, i8, i8)) -> Option<(i8, i8, i8)> {
+        Some((self.0.checked_add(other.0)?, self.1.checked_add(other.1)?, self.2.checked_add(other.2)?))
+    }
+}
+
+/// Determine the mathematical vectors that neighbour an other.
+pub trait Neighbours<T> {
+    /// the number of faces of a cell, i.e. the number of neighbours of a cell
+    /// away from the edge of the range of the coordinates
+    fn faces() -> usize where Self: Sized;
+
+    /// vectors that differ from self by one in a single coordinate, leaving
+    /// out those beyond the range of the coordinates
+    fn neighbours(&self) -> Vec<T>;
+
+    /// vectors of the cells that touch the cell at self with the `connectivity`,
+    /// leaving out those beyond the range of the coordinates
+    fn adjacent(&self, connectivity: &Connectivity) -> Vec<T>;
+}
+
+impl Neighbours<(i8, i8)> for (i8, i8) {
+    fn faces() -> usize {
+        4
+    }
+
+    fn neighbours(&self) -> Vec<(i8, i8)> {
+        let (x, y) = *self;
+        let mut result = vec!();
+        result.extend(x.checked_sub(1).map(|x| (x, y)));
+        result.extend(x.checked_add(1).map(|x| (x, y)));
+        result.extend(y.checked_sub(1).map(|y| (x, y)));
+        result.extend(y.checked_add(1).map(|y| (x, y)));
+        result
+    }
+
+    fn adjacent(&self, connectivity: &Connectivity) -> Vec<(i8, i8)> {
+        match connectivity {
+            Connectivity::Face | Connectivity::Edge => self.neighbours(),
+            Connectivity::Vertex => {
+                let (x, y) = *self;
+                let mut result = vec!();
+                for dx in -1i8..=1 {
+                    for dy in -1i8..=1 {
+                        if (dx, dy) != (0, 0) {
+                            if let (Some(x), Some(y)) = (x.checked_add(dx), y.checked_add(dy)) {
+                                result.push((x, y));
+                            }
+                        }
+                    }
+                }
+                result
+            },
+        }
+    }
+}
+
+impl Neighbours<(i8, i8, i8)> for (i8, i8, i8) {
+    fn faces() -> usize {
+        6
+    }
+
+    fn neighbours(&self) -> Vec<(i8, i8, i8)> {
+        let (x, y, z) = *self;
+        let mut result = vec!();
+        result.extend(x.checked_sub(1).map(|x| (x, y, z)));
+        result.extend(x.checked_add(1).map(|x| (x, y, z)));
+        result.extend(y.checked_sub(1).map(|y| (x, y, z)));
+        result.extend(y.checked_add(1).map(|y| (x, y, z)));
+        result.extend(z.checked_sub(1).map(|z| (x, y, z)));
+        result.extend(z.checked_add(1).map(|z| (x, y, z)));
+        result
+    }
+
+    fn adjacent(&self, connectivity: &Connectivity) -> Vec<(i8, i8, i8)> {
+        let shared = match connectivity {
+            Connectivity::Face => 1,
+            Connectivity::Edge => 2,
+            Connectivity::Vertex => 3,
+        };
+        let (x, y, z) = *self;
+        let mut result = vec!();
+        for dx in -1i8..=1 {
+            for dy in -1i8..=1 {
+                for dz in -1i8..=1 {
+                    let changed = dx.abs() + dy.abs() + dz.abs();
+                    if changed > 0 && changed <= shared {
+                        if let (Some(x), Some(y), Some(z)) = (x.checked_add(dx), y.checked_add(dy), z.checked_add(dz)) {
+                            result.push((x, y, z));
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Determine boxes spanned by mathematical vectors.
+pub trait Bounds<T> {
+    /// coordinate wise minimum of self and other
+    fn minimum(&self, other: &T) -> T;
+
+    /// coordinate wise maximum of self and other
+    fn maximum(&self, other: &T) -> T;
+
+    /// whether self lies on a face of the box with corners lower and upper
+    fn on_boundary(&self, lower: &T, upper: &T) -> bool;
+
+    /// all vectors in the box with corners self and upper, inclusive
+    fn span(&self, upper: &T) -> Vec<T>;
+}
+
+impl Bounds<(i8, i8)> for (i8, i8) {
+    fn minimum(&self, other: &(i8, i8)) -> (i8, i8) {
+        (self.0.min(other.0), self.1.min(other.1))
+    }
+
+    fn maximum(&self, other: &(i8, i8)) -> (i8, i8) {
+        (self.0.max(other.0), self.1.max(other.1))
+    }
+
+    fn on_boundary(&self, lower: &(i8, i8), upper: &(i8, i8)) -> bool {
+        self.0 == lower.0 || self.0 == upper.0 || self.1 == lower.1 || self.1 == upper.1
+    }
+
+    fn span(&self, upper: &(i8, i8)) -> Vec<(i8, i8)> {
+        let mut result = vec!();
+        for x in self.0..=upper.0 {
+            for y in self.1..=upper.1 {
+                result.push((x, y));
+            }
+        }
+        result
+    }
+}
+
+impl Bounds<(i8, i8, i8)> for (i8, i8, i8) {
+    fn minimum(&self, other: &(i8, i8, i8)) -> (i8, i8, i8) {
+        (self.0.min(other.0), self.1.min(other.1), self.2.min(other.2))
+    }
+
+    fn maximum(&self, other: &(i8, i8, i8)) -> (i8, i8, i8) {
+        (self.0.max(other.0), self.1.max(other.1), self.2.max(other.2))
+    }
+
+    fn on_boundary(&self, lower: &(i8, i8, i8), upper: &(i8, i8, i8)) -> bool {
+        self.0 == lower.0 || self.0 == upper.0 || self.1 == lower.1 || self.1 == upper.1 || self.2 == lower.2 || self.2 == upper.2
+    }
+
+    fn span(&self, upper: &(i8, i8, i8)) -> Vec<(i8, i8, i8)> {
+        let mut result = vec!();
+        for x in self.0..=upper.0 {
+            for y in self.1..=upper.1 {
+                for z in self.2..=upper.2 {
+                    result.push((x, y, z));
+                }
+            }
+        }
+        result
+    }
 }
 
 #[cfg(test)]
@@ -85,4 +267,11 @@ mod tests {
 
         assert_eq!(a, (5, 6, 7))
     }
+
+    #[test]
+    fn neighbours_should_stay_in_range() {
+        assert_eq!((0i8, 0i8).neighbours(), vec!((-1, 0), (1, 0), (0, -1), (0, 1)));
+        assert_eq!((i8::MIN, i8::MAX).neighbours(), vec!((i8::MIN + 1, i8::MAX), (i8::MIN, i8::MAX - 1)));
+        assert_eq!((i8::MAX, 0i8, i8::MIN).neighbours().len(), 4);
+    }
 }
@@ -0,0 +1,283 @@
+//! Verification of solutions, independent of the search.
+//!
+//! Solutions imported from other tools, or produced by the solver, can be
+//! checked against the `Target` and the `Bag` they claim to solve.
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+
+use super::piece::{CubeSymmetryIterator, MinimumPosition, Normalizable, Piece, Position, Template, Transformable, Translatable};
+use super::pieces::Bag;
+use super::solver::{Placement, Solution, Target};
+use super::vector::{VectorAdd, VectorDifference};
+
+/// Reasons why a `Solution` does not solve a packing problem.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VerificationError<T> {
+    /// A piece covers a position that is not part of the target.
+    OutsideTarget {
+        /// Index of the piece in the solution.
+        piece: usize,
+        /// The position outside of the target.
+        position: Position<T>,
+    },
+    /// A position of the target is covered by more than one piece.
+    Overlap(Position<T>),
+    /// A position of the target is not covered by any piece.
+    Uncovered(Position<T>),
+    /// A piece is not a rigid image of any template in the bag.
+    UnknownShape {
+        /// Index of the piece in the solution.
+        piece: usize,
+    },
+    /// A piece matches templates in the bag, but all of them are used up.
+    ExceedsMultiplicity {
+        /// Index of the piece in the solution.
+        piece: usize,
+    },
+    /// The template, symmetry and translation of a placement do not produce
+    /// its piece.
+    InvalidPlacement {
+        /// Index of the piece in the solution.
+        piece: usize,
+    },
+}
+
+impl<T> Display for VerificationError<T> where T: Debug {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            VerificationError::OutsideTarget { piece, position } => write!(f, "piece {} covers {:?}, which is not part of the target", piece, position.coordinates()),
+            VerificationError::Overlap(position) => write!(f, "cell {:?} is covered more than once", position.coordinates()),
+            VerificationError::Uncovered(position) => write!(f, "cell {:?} is not covered", position.coordinates()),
+            VerificationError::UnknownShape { piece } => write!(f, "piece {} does not match any template in the bag", piece),
+            VerificationError::ExceedsMultiplicity { piece } => write!(f, "piece {} matches only templates that are used up", piece),
+            VerificationError::InvalidPlacement { piece } => write!(f, "placement {} does not produce its piece", piece),
+        }
+    }
+}
+
+impl<T> Error for VerificationError<T> where T: Debug {}
+
+/// Check that the `Solution` packs the `Target` with pieces from the `Bag`.
+///
+/// Every piece must be a rotation, or a reflection where the space admits
+/// it, followed by a translation of a `Template`, and no `Template` may be
+/// used more often than its count. Together the pieces must cover every
+/// position of the `Target` exactly once. Pieces are matched by shape only,
+/// their names are ignored.
+///
+/// A piece that states its template must be a rigid image of that template.
+/// When it states the symmetry and the translation as well, the space must
+/// admit the symmetry and both together must move the template onto the piece.
+pub fn verify<T>(target: &Target<T>, bag: &Bag<T>, solution: &Solution<T>) -> Result<(), VerificationError<T>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
+    check_coverage(target, solution)?;
+    check_templates(bag, solution)
+}
+
+fn check_coverage<T>(target: &Target<T>, solution: &Solution<T>) -> Result<(), VerificationError<T>> where T: Clone + Ord {
+    let cells: BTreeSet<Position<T>> = target.iter().collect();
+    let mut covered: BTreeSet<Position<T>> = BTreeSet::new();
+    for (index, piece) in solution.iter().enumerate() {
+        for position in piece.iter() {
+            if !cells.contains(&position) {
+                return Err(VerificationError::OutsideTarget { piece: index, position });
+            }
+            if !covered.insert(position.clone()) {
+                return Err(VerificationError::Overlap(position));
+            }
+        }
+    }
+    match cells.into_iter().find(|position| !covered.contains(position)) {
+        Some(position) => Err(VerificationError::Uncovered(position)),
+        None => Ok(()),
+    }
+}
+
+/// Assign every piece to a `Template` of the same shape that has pieces left,
+/// or to the `Template` its `Placement` states.
+///
+/// Templates that accept a piece have the same images, so they accept the
+/// same pieces and assigning greedily never rejects a valid solution.
+fn check_templates<T>(bag: &Bag<T>, solution: &Solution<T>) -> Result<(), VerificationError<T>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
+    let shapes: Vec<BTreeSet<Vec<Position<T>>>> = bag.iter().map(|(_, template)| images(template)).collect();
+    let mut counts: Vec<usize> = bag.iter().map(|(count, _)| *count).collect();
+
+    for (index, placement) in solution.placements().iter().enumerate() {
+        let shape = normalized(placement.piece());
+        let matching: Vec<usize> = match placement.template() {
+            Some(entry) => {
+                if !shapes.get(entry).map(|images| images.contains(&shape)).unwrap_or(false) || !reproduces(bag, placement) {
+                    return Err(VerificationError::InvalidPlacement { piece: index });
+                }
+                vec!(entry)
+            },
+            None if placement.symmetry().is_some() || placement.translation().is_some() => {
+                return Err(VerificationError::InvalidPlacement { piece: index });
+            },
+            None => (0..shapes.len()).filter(|&entry| shapes[entry].contains(&shape)).collect(),
+        };
+        if matching.is_empty() {
+            return Err(VerificationError::UnknownShape { piece: index });
+        }
+        match matching.into_iter().find(|&entry| counts[entry] > 0) {
+            Some(entry) => counts[entry] -= 1,
+            None => return Err(VerificationError::ExceedsMultiplicity { piece: index }),
+        }
+    }
+    Ok(())
+}
+
+/// The rigid images of a `Template` that the space admits, each moved so that
+/// its minimum position is the reference position.
+fn images<T>(template: &Template<T>) -> BTreeSet<Vec<Position<T>>> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
+    CubeSymmetryIterator::new()
+        .filter(T::admits)
+        .map(|symmetry| {
+            let positions: Vec<Position<T>> = template.iter()
+                .map(|mut position| {
+                    position.transform(&symmetry);
+                    position
+                })
+                .collect();
+            normalized(&Piece::new(positions))
+        })
+        .collect()
+}
+
+/// Determine if the symmetry and the translation of the `Placement`, when
+/// both are stated, move its `Template` onto its piece.
+fn reproduces<T>(bag: &Bag<T>, placement: &Placement<T>) -> bool where T: Clone + PartialOrd + Ord + Transformable + VectorAdd<T> {
+    let (symmetry, translation) = match (placement.symmetry(), placement.translation()) {
+        (Some(symmetry), Some(translation)) => (symmetry, translation),
+        (None, None) => return true,
+        _ => return false,
+    };
+    let template = match placement.template().and_then(|entry| bag.iter().nth(entry)) {
+        Some((_, template)) => template,
+        None => return false,
+    };
+    if !T::admits(symmetry) {
+        return false;
+    }
+    let mut piece = Piece::new(template.iter().collect());
+    piece.transform(symmetry);
+    piece.checked_translate(translation).is_ok() && piece.iter().eq(placement.piece().iter())
+}
+
+/// Positions of a `Piece`, moved so that its minimum position is the reference position.
+fn normalized<T>(piece: &Piece<T>) -> Vec<Position<T>> where T: Clone + PartialOrd + Ord + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
+    let mut piece = piece.clone();
+    if let Some(minimum) = piece.minimum_position() {
+        piece.translate(&minimum.to_reference());
+    }
+    piece.iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::catalogue::{one_of_each, pentominoes};
+    use super::super::super::util::target::rectangle;
+    use super::super::piece::{CubeSymmetry, Translation};
+    use super::super::solver::solve_while;
+    use super::*;
+
+    fn domino() -> Template<(i8, i8)> {
+        Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))
+    }
+
+    fn solution(pieces: Vec<Vec<(i8, i8)>>) -> Solution<(i8, i8)> {
+        pieces.into_iter().fold(Solution::empty(), |solution, cells| {
+            solution.record(&Piece::new(cells.into_iter().map(Position::from).collect()))
+        })
+    }
+
+    #[test]
+    fn should_accept_a_valid_solution() {
+        let target = rectangle(2, 2);
+        let bag = Bag::new(vec!((2, domino())));
+
+        assert_eq!(verify(&target, &bag, &solution(vec!(vec!((0, 0), (0, 1)), vec!((1, 0), (1, 1))))), Ok(()));
+    }
+
+    #[test]
+    fn should_refuse_faulty_coverage() {
+        let target = rectangle(2, 2);
+        let bag = Bag::new(vec!((3, domino())));
+
+        assert_eq!(
+            verify(&target, &bag, &solution(vec!(vec!((0, 0), (0, 1)), vec!((1, 1), (1, 2))))),
+            Err(VerificationError::OutsideTarget { piece: 1, position: Position::d2(1, 2) }),
+        );
+        assert_eq!(
+            verify(&target, &bag, &solution(vec!(vec!((0, 0), (0, 1)), vec!((0, 1), (1, 1))))),
+            Err(VerificationError::Overlap(Position::d2(0, 1))),
+        );
+        assert_eq!(
+            verify(&target, &bag, &solution(vec!(vec!((0, 0), (0, 1))))),
+            Err(VerificationError::Uncovered(Position::d2(1, 0))),
+        );
+    }
+
+    #[test]
+    fn should_refuse_pieces_that_are_not_in_the_bag() {
+        let target = rectangle(2, 2);
+
+        assert_eq!(
+            verify(&target, &Bag::new(vec!((2, domino()))), &solution(vec!(vec!((0, 0), (0, 1), (1, 1)), vec!((1, 0))))),
+            Err(VerificationError::UnknownShape { piece: 0 }),
+        );
+        assert_eq!(
+            verify(&target, &Bag::new(vec!((1, domino()))), &solution(vec!(vec!((0, 0), (0, 1)), vec!((1, 0), (1, 1))))),
+            Err(VerificationError::ExceedsMultiplicity { piece: 1 }),
+        );
+    }
+
+    #[test]
+    fn should_accept_reflections_only_where_the_space_admits_them() {
+        let l = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0), Position::d2(2, 1)));
+        let target = rectangle(3, 2);
+        let bag = Bag::new(vec!((1, l), (1, domino())));
+
+        assert_eq!(verify(&target, &bag, &solution(vec!(vec!((0, 0), (1, 0), (2, 0), (0, 1)), vec!((1, 1), (2, 1))))), Ok(()));
+
+        let screw = Template::new(vec!(Position::new(0, 0, 0), Position::new(1, 0, 0), Position::new(1, 1, 0), Position::new(1, 1, 1)));
+        let mirrored = Piece::new(vec!(Position::new(1, 0, 0), Position::new(0, 0, 0), Position::new(0, 1, 0), Position::new(0, 1, 1)));
+        let target = Target::new(mirrored.iter().collect());
+
+        assert_eq!(
+            verify(&target, &Bag::new(vec!((1, screw))), &Solution::empty().record(&mirrored)),
+            Err(VerificationError::UnknownShape { piece: 0 }),
+        );
+    }
+
+    #[test]
+    fn should_refuse_placements_that_do_not_produce_their_piece() {
+        let target = rectangle(2, 1);
+        let bag = Bag::new(vec!((1, domino())));
+        let placed = |template: usize, symmetry: CubeSymmetry, translation: (i8, i8)| {
+            let piece = Piece::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+            Solution::empty().place(&Placement::new(template, symmetry, Translation::from(translation), piece))
+        };
+
+        assert_eq!(verify(&target, &bag, &placed(0, CubeSymmetry::E0123, (0, 0))), Ok(()));
+        assert_eq!(verify(&target, &bag, &placed(7, CubeSymmetry::E0123, (0, 0))), Err(VerificationError::InvalidPlacement { piece: 0 }));
+        assert_eq!(verify(&target, &bag, &placed(0, CubeSymmetry::E0123, (1, 0))), Err(VerificationError::InvalidPlacement { piece: 0 }));
+        assert_eq!(verify(&target, &bag, &placed(0, CubeSymmetry::E0132, (0, 0))), Err(VerificationError::InvalidPlacement { piece: 0 }));
+    }
+
+    #[test]
+    fn solutions_of_the_solver_should_verify() {
+        let target = rectangle(6, 10);
+        let bag = one_of_each(pentominoes());
+        let mut solutions = vec!();
+
+        solve_while(&target, bag.clone(), &mut |solution| {
+            solutions.push(solution);
+            solutions.len() < 2
+        }).unwrap();
+
+        assert_eq!(solutions.len(), 2);
+        assert!(solutions.iter().all(|solution| verify(&target, &bag, solution).is_ok()));
+    }
+}
@@ -0,0 +1,280 @@
+//! Rendering spatial solutions as isometric SVG pictures.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::{Colour, Renderer};
+use super::super::puzzle::solver::{Solution, Target};
+
+/// Renders a spatial `Solution` or `Target` as an isometric SVG picture.
+///
+/// The picture is seen from the direction `(1, 1, 1)`, so the faces pointing
+/// along the positive axes are visible. The z-axis points up.
+#[derive(Debug, Clone)]
+pub struct IsometricRenderer {
+    cell_size: u32,
+    explosion: f64,
+    columns: usize,
+}
+
+impl IsometricRenderer {
+    /// Create an `IsometricRenderer` with cubes of 20 units that keeps pieces
+    /// together, and tiles sheets in 4 columns.
+    pub fn new() -> IsometricRenderer {
+        IsometricRenderer { cell_size: 20, explosion: 0.0, columns: 4 }
+    }
+
+    /// Create an `IsometricRenderer` from this one that draws cubes of `cell_size` units.
+    pub fn with_cell_size(self, cell_size: u32) -> IsometricRenderer {
+        IsometricRenderer { cell_size, ..self }
+    }
+
+    /// Create an `IsometricRenderer` from this one that draws an exploded view.
+    ///
+    /// Every piece is pushed away from the centre by `explosion` times the
+    /// distance between its own centre and the centre of all pieces.
+    pub fn with_explosion(self, explosion: f64) -> IsometricRenderer {
+        IsometricRenderer { explosion, ..self }
+    }
+
+    /// Create an `IsometricRenderer` from this one that tiles sheets in `columns` columns.
+    pub fn with_columns(self, columns: usize) -> IsometricRenderer {
+        IsometricRenderer { columns: columns.max(1), ..self }
+    }
+
+    /// Render a sheet that tiles all `solutions` in a grid.
+    pub fn render_sheet(&self, solutions: &[Solution<(i8, i8, i8)>]) -> String {
+        let drawings: Vec<Drawing> = solutions.iter().map(|solution| self.drawing(&groups(solution), &colours(solution))).collect();
+        let tile_width = drawings.iter().map(|drawing| drawing.width()).fold(0.0, f64::max);
+        let tile_height = drawings.iter().map(|drawing| drawing.height()).fold(0.0, f64::max);
+        let gap = self.cell_size as f64;
+        let columns = self.columns.min(drawings.len()).max(1);
+        let rows = drawings.len().div_ceil(columns);
+
+        let mut body = String::new();
+        for (index, drawing) in drawings.iter().enumerate() {
+            let column = (index % columns) as f64;
+            let row = (index / columns) as f64;
+            let origin = (column * (tile_width + gap) - drawing.min.0, row * (tile_height + gap) - drawing.min.1);
+            writeln!(body, r#"<g transform="translate({:.2},{:.2})">"#, origin.0, origin.1).unwrap();
+            body.push_str(&drawing.body);
+            body.push_str("</g>\n");
+        }
+
+        let width = columns as f64 * (tile_width + gap) - gap;
+        let height = rows as f64 * (tile_height + gap) - gap;
+        self.document(width.max(0.0), height.max(0.0), (0.0, 0.0), &body)
+    }
+
+    fn picture(&self, groups: &[Vec<(i32, i32, i32)>], colours: &[Colour]) -> String {
+        let drawing = self.drawing(groups, colours);
+
+        self.document(drawing.width(), drawing.height(), (-drawing.min.0, -drawing.min.1), &drawing.body)
+    }
+
+    fn document(&self, width: f64, height: f64, origin: (f64, f64), body: &str) -> String {
+        let margin = self.cell_size as f64 / 2.0;
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.0}" height="{h:.0}" viewBox="0 0 {w:.0} {h:.0}">"#,
+            w = (width + 2.0 * margin).ceil(),
+            h = (height + 2.0 * margin).ceil(),
+        ).unwrap();
+        writeln!(svg, r#"<g transform="translate({:.2},{:.2})">"#, margin + origin.0, margin + origin.1).unwrap();
+        svg.push_str(body);
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+
+    fn drawing(&self, groups: &[Vec<(i32, i32, i32)>], colours: &[Colour]) -> Drawing {
+        let all: Vec<(i32, i32, i32)> = groups.iter().flatten().cloned().collect();
+        let exploded = self.explosion != 0.0;
+        let centre = mean(&all);
+        let occupied: HashSet<(i32, i32, i32)> = all.iter().cloned().collect();
+
+        let mut faces: Vec<Face> = vec!();
+        for (group, cubes) in groups.iter().enumerate() {
+            let own: HashSet<(i32, i32, i32)> = cubes.iter().cloned().collect();
+            let blocking = if exploded { &own } else { &occupied };
+            let middle = mean(cubes);
+            let offset = (
+                (middle.0 - centre.0) * self.explosion,
+                (middle.1 - centre.1) * self.explosion,
+                (middle.2 - centre.2) * self.explosion,
+            );
+
+            for &(x, y, z) in cubes {
+                let depth = (x + y + z) as f64 + offset.0 + offset.1 + offset.2;
+                let corner = |dx: i32, dy: i32, dz: i32| {
+                    ((x + dx) as f64 + offset.0, (y + dy) as f64 + offset.1, (z + dz) as f64 + offset.2)
+                };
+                let sides = [
+                    ((x, y, z + 1), [corner(0, 0, 1), corner(1, 0, 1), corner(1, 1, 1), corner(0, 1, 1)], 1.0),
+                    ((x + 1, y, z), [corner(1, 0, 0), corner(1, 1, 0), corner(1, 1, 1), corner(1, 0, 1)], 0.8),
+                    ((x, y + 1, z), [corner(0, 1, 0), corner(1, 1, 0), corner(1, 1, 1), corner(0, 1, 1)], 0.6),
+                ];
+                for (neighbour, corners, shade) in sides.iter() {
+                    if !blocking.contains(neighbour) {
+                        faces.push(Face {
+                            depth,
+                            points: corners.iter().map(|&point| self.project(point)).collect(),
+                            fill: colours[group].shade(*shade),
+                        });
+                    }
+                }
+            }
+        }
+        faces.sort_by(|a, b| a.depth.partial_cmp(&b.depth).unwrap());
+
+        let points: Vec<&(f64, f64)> = faces.iter().flat_map(|face| face.points.iter()).collect();
+        let min = (points.iter().map(|p| p.0).fold(0.0, f64::min), points.iter().map(|p| p.1).fold(0.0, f64::min));
+        let max = (points.iter().map(|p| p.0).fold(0.0, f64::max), points.iter().map(|p| p.1).fold(0.0, f64::max));
+
+        let mut body = String::new();
+        for face in faces {
+            let points: Vec<String> = face.points.iter().map(|p| format!("{:.2},{:.2}", p.0, p.1)).collect();
+            writeln!(
+                body,
+                r#"<polygon points="{}" fill="{}" stroke="{}" stroke-width="1" stroke-linejoin="round"/>"#,
+                points.join(" "),
+                face.fill.hex(),
+                face.fill.shade(0.6).hex(),
+            ).unwrap();
+        }
+
+        Drawing { body, min, max }
+    }
+
+    fn project(&self, (x, y, z): (f64, f64, f64)) -> (f64, f64) {
+        let size = self.cell_size as f64;
+        let horizontal = (x - y) * size * 3f64.sqrt() / 2.0;
+        let vertical = (x + y) * size / 2.0 - z * size;
+
+        (horizontal, vertical)
+    }
+}
+
+impl Default for IsometricRenderer {
+    fn default() -> Self {
+        IsometricRenderer::new()
+    }
+}
+
+impl Renderer<Solution<(i8, i8, i8)>> for IsometricRenderer {
+    fn render(&self, solution: &Solution<(i8, i8, i8)>) -> String {
+        self.picture(&groups(solution), &colours(solution))
+    }
+}
+
+impl Renderer<Target<(i8, i8, i8)>> for IsometricRenderer {
+    fn render(&self, target: &Target<(i8, i8, i8)>) -> String {
+        let cubes: Vec<(i32, i32, i32)> = target.iter().map(|position| widen(*position.coordinates())).collect();
+
+        self.picture(&[cubes], &[Colour(192, 192, 192)])
+    }
+}
+
+/// Polygons of the visible faces, with the extent of their points.
+struct Drawing {
+    body: String,
+    min: (f64, f64),
+    max: (f64, f64),
+}
+
+impl Drawing {
+    fn width(&self) -> f64 {
+        self.max.0 - self.min.0
+    }
+
+    fn height(&self) -> f64 {
+        self.max.1 - self.min.1
+    }
+}
+
+/// Visible face of a cube, projected on the picture.
+struct Face {
+    depth: f64,
+    points: Vec<(f64, f64)>,
+    fill: Colour,
+}
+
+/// The cubes of every piece of the `Solution`.
+fn groups(solution: &Solution<(i8, i8, i8)>) -> Vec<Vec<(i32, i32, i32)>> {
+    solution.pieces()
+        .iter()
+        .map(|piece| piece.iter().map(|position| widen(*position.coordinates())).collect())
+        .collect()
+}
+
+/// A distinct colour for every piece of the `Solution`.
+fn colours(solution: &Solution<(i8, i8, i8)>) -> Vec<Colour> {
+    (0..solution.pieces().len()).map(Colour::distinct).collect()
+}
+
+fn widen((x, y, z): (i8, i8, i8)) -> (i32, i32, i32) {
+    (x as i32, y as i32, z as i32)
+}
+
+/// The centre of mass of unit cubes.
+fn mean(cubes: &[(i32, i32, i32)]) -> (f64, f64, f64) {
+    let n = cubes.len().max(1) as f64;
+    let sum = cubes.iter().fold((0.0, 0.0, 0.0), |sum, &(x, y, z)| {
+        (sum.0 + x as f64 + 0.5, sum.1 + y as f64 + 0.5, sum.2 + z as f64 + 0.5)
+    });
+
+    (sum.0 / n, sum.1 / n, sum.2 / n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::piece::{Piece, Position};
+    use super::super::super::util::target::brick;
+    use super::*;
+
+    fn solution() -> Solution<(i8, i8, i8)> {
+        Solution::empty()
+            .record(&Piece::new(vec!(Position::new(0, 0, 0))))
+            .record(&Piece::new(vec!(Position::new(1, 0, 0))))
+    }
+
+    #[test]
+    fn should_only_draw_visible_faces() {
+        let svg = IsometricRenderer::new().render(&solution());
+
+        assert_eq!(svg.matches("<polygon").count(), 5);
+    }
+
+    #[test]
+    fn exploded_view_should_draw_faces_between_pieces() {
+        let svg = IsometricRenderer::new().with_explosion(1.0).render(&solution());
+
+        assert_eq!(svg.matches("<polygon").count(), 6);
+    }
+
+    #[test]
+    fn should_shade_faces_of_a_piece() {
+        let svg = IsometricRenderer::new().render(&solution());
+        let colour = Colour::distinct(0);
+
+        assert!(svg.contains(&format!(r#"fill="{}""#, colour.hex())));
+        assert!(svg.contains(&format!(r#"fill="{}""#, colour.shade(0.6).hex())));
+    }
+
+    #[test]
+    fn should_tile_solutions_on_a_single_sheet() {
+        let solutions = vec!(solution(), solution(), solution());
+
+        let svg = IsometricRenderer::new().with_columns(2).render_sheet(&solutions);
+
+        assert_eq!(svg.matches("<svg").count(), 1);
+        assert_eq!(svg.matches("<polygon").count(), 15);
+    }
+
+    #[test]
+    fn should_render_a_target() {
+        let svg = IsometricRenderer::new().with_cell_size(10).render(&brick(2, 2, 2));
+
+        assert_eq!(svg.matches("<polygon").count(), 12);
+    }
+}
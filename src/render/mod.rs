@@ -0,0 +1,173 @@
+//! Presenting solutions of packing problems.
+
+pub mod isometric;
+pub mod svg;
+pub mod text;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::puzzle::piece::Piece;
+
+/// Contract how to render a subject, e.g. a `Solution`, into a `String`.
+pub trait Renderer<S> {
+    /// Render the subject.
+    fn render(&self, subject: &S) -> String;
+}
+
+/// Coordinate axes of space.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Axis {
+    /// The first coordinate.
+    X,
+    /// The second coordinate.
+    Y,
+    /// The third coordinate.
+    Z,
+}
+
+impl Axis {
+    /// Split coordinates into the coordinate along this axis and the remaining two, in order.
+    pub fn split(self, (x, y, z): (i8, i8, i8)) -> (i8, (i8, i8)) {
+        match self {
+            Axis::X => (x, (y, z)),
+            Axis::Y => (y, (x, z)),
+            Axis::Z => (z, (x, y)),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Axis::X => "x",
+            Axis::Y => "y",
+            Axis::Z => "z",
+        }
+    }
+}
+
+/// A colour in RGB.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct Colour(pub u8, pub u8, pub u8);
+
+impl Colour {
+    /// The `index`th colour of a palette of distinct colours.
+    ///
+    /// Hues are spread by the golden angle, so any number of consecutive
+    /// colours is well separated.
+    pub fn distinct(index: usize) -> Colour {
+        let hue = (index as f64 * 137.507_764) % 360.0;
+        Colour::from_hsl(hue, 0.65, 0.55)
+    }
+
+    fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Colour {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let sector = hue / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+
+        Colour(channel(r), channel(g), channel(b))
+    }
+
+    /// This colour with every channel scaled by `factor`.
+    pub fn shade(self, factor: f64) -> Colour {
+        let channel = |value: u8| (value as f64 * factor).round().clamp(0.0, 255.0) as u8;
+
+        Colour(channel(self.0), channel(self.1), channel(self.2))
+    }
+
+    /// Hexadecimal notation, e.g. `#ff8800`.
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Escape text for use in XML documents.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const AUTOMATIC_LABELS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Determine a label for each `Piece`.
+///
+/// Named pieces are labelled with their name. A name that is shared by several
+/// pieces gets a number as suffix, e.g. `I1` and `I2`, so that every label is
+/// unique. The other pieces are assigned a letter that is not used as a label,
+/// or a number when the letters run out.
+pub(crate) fn labels<T>(pieces: &[&Piece<T>]) -> Vec<String> where T: PartialOrd + Ord + Clone {
+    let mut occurrences: BTreeMap<&str, usize> = BTreeMap::new();
+    for name in pieces.iter().filter_map(|piece| piece.name()) {
+        *occurrences.entry(name).or_insert(0) += 1;
+    }
+    let mut taken: BTreeSet<String> = occurrences.keys().map(|name| name.to_string()).collect();
+    let named: Vec<Option<String>> = pieces
+        .iter()
+        .map(|piece| {
+            piece.name().map(|name| {
+                if occurrences[name] == 1 {
+                    return name.to_string();
+                }
+                let label = (1..)
+                    .map(|suffix| format!("{}{}", name, suffix))
+                    .find(|label| !taken.contains(label))
+                    .unwrap();
+                taken.insert(label.clone());
+                label
+            })
+        })
+        .collect();
+    let mut available = AUTOMATIC_LABELS
+        .chars()
+        .map(|c| c.to_string())
+        .filter(|label| !taken.contains(label));
+
+    named
+        .into_iter()
+        .enumerate()
+        .map(|(index, label)| label.unwrap_or_else(|| available.next().unwrap_or_else(|| index.to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::puzzle::piece::Position;
+    use super::super::puzzle::solver::Solution;
+    use super::*;
+
+    #[test]
+    fn labels_should_prefer_names_and_avoid_clashes() {
+        let solution = Solution::empty()
+            .record(&Piece::new(vec!(Position::d2(0, 0))))
+            .record(&Piece::named(vec!(Position::d2(1, 0)), "A"))
+            .record(&Piece::new(vec!(Position::d2(2, 0))));
+
+        assert_eq!(labels(&solution.pieces()), vec!("B", "A", "C"));
+    }
+
+    #[test]
+    fn labels_should_distinguish_pieces_with_the_same_name() {
+        let solution = Solution::empty()
+            .record(&Piece::named(vec!(Position::d2(0, 0)), "I"))
+            .record(&Piece::named(vec!(Position::d2(1, 0)), "I1"))
+            .record(&Piece::named(vec!(Position::d2(2, 0)), "I"))
+            .record(&Piece::new(vec!(Position::d2(3, 0))));
+
+        assert_eq!(labels(&solution.pieces()), vec!("I2", "I1", "I3", "A"));
+    }
+
+    #[test]
+    fn palette_should_start_with_a_red() {
+        assert_eq!(Colour::distinct(0).hex(), "#d74242");
+    }
+}
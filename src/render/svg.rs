@@ -0,0 +1,307 @@
+//! Rendering planar solutions as SVG.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::{escape, labels, Colour, Renderer};
+use super::super::puzzle::piece::Piece;
+use super::super::puzzle::solver::Solution;
+
+/// Renders a planar `Solution` as an SVG document.
+///
+/// Every piece is drawn as a filled outline with a colour of its own, determined
+/// by the index of the piece in the solution. Like the `TextRenderer`, `y`
+/// increases downwards.
+#[derive(Debug, Clone)]
+pub struct SvgRenderer {
+    cell_size: u32,
+    labels: bool,
+    columns: usize,
+}
+
+impl SvgRenderer {
+    /// Create a `SvgRenderer` with cells of 20 units, without labels, that
+    /// tiles sheets in 4 columns.
+    pub fn new() -> SvgRenderer {
+        SvgRenderer { cell_size: 20, labels: false, columns: 4 }
+    }
+
+    /// Create a `SvgRenderer` from this one that draws cells of `cell_size` units.
+    pub fn with_cell_size(self, cell_size: u32) -> SvgRenderer {
+        SvgRenderer { cell_size, ..self }
+    }
+
+    /// Create a `SvgRenderer` from this one that labels each piece.
+    pub fn with_labels(self) -> SvgRenderer {
+        SvgRenderer { labels: true, ..self }
+    }
+
+    /// Create a `SvgRenderer` from this one that tiles sheets in `columns` columns.
+    pub fn with_columns(self, columns: usize) -> SvgRenderer {
+        SvgRenderer { columns: columns.max(1), ..self }
+    }
+
+    /// Render a sheet that tiles all `solutions` in a grid.
+    pub fn render_sheet(&self, solutions: &[Solution<(i8, i8)>]) -> String {
+        let bounds: Vec<Bounds> = solutions.iter().map(|solution| Bounds::of(&solution.pieces())).collect();
+        let tile_width = bounds.iter().map(|b| b.width()).max().unwrap_or(0);
+        let tile_height = bounds.iter().map(|b| b.height()).max().unwrap_or(0);
+        let columns = self.columns.min(solutions.len()).max(1);
+        let rows = solutions.len().div_ceil(columns);
+
+        let mut body = String::new();
+        for (index, solution) in solutions.iter().enumerate() {
+            let column = (index % columns) as i32;
+            let row = (index / columns) as i32;
+            let origin = (
+                column * (tile_width + 1) - bounds[index].min.0,
+                row * (tile_height + 1) - bounds[index].min.1,
+            );
+            self.pieces(&mut body, &solution.pieces(), origin);
+        }
+
+        let width = columns as i32 * (tile_width + 1) - 1;
+        let height = rows as i32 * (tile_height + 1) - 1;
+        self.document(width.max(0), height.max(0), &body)
+    }
+
+    fn document(&self, width: i32, height: i32, body: &str) -> String {
+        let size = self.cell_size as i32;
+        let margin = size / 2;
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width * size + 2 * margin,
+            h = height * size + 2 * margin,
+        ).unwrap();
+        writeln!(svg, r#"<g transform="translate({m},{m})">"#, m = margin).unwrap();
+        svg.push_str(body);
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+
+    fn pieces(&self, svg: &mut String, pieces: &[&Piece<(i8, i8)>], origin: (i32, i32)) {
+        let size = self.cell_size as i32;
+        let labels = labels(pieces);
+        for (index, (piece, label)) in pieces.iter().zip(labels.iter()).enumerate() {
+            let cells: Vec<(i32, i32)> = piece
+                .iter()
+                .map(|position| {
+                    let (x, y) = *position.coordinates();
+                    (origin.0 + x as i32, origin.1 + y as i32)
+                })
+                .collect();
+
+            let mut path = String::new();
+            for outline in outlines(&cells) {
+                let mut points = outline.iter();
+                if let Some(&(x, y)) = points.next() {
+                    write!(path, "M{},{}", x * size, y * size).unwrap();
+                }
+                for &(x, y) in points {
+                    write!(path, "L{},{}", x * size, y * size).unwrap();
+                }
+                path.push('Z');
+            }
+            writeln!(
+                svg,
+                r##"<path d="{}" fill="{}" fill-rule="evenodd" stroke="#000000" stroke-width="{}"/>"##,
+                path,
+                Colour::distinct(index).hex(),
+                (size as f64 / 10.0).max(1.0),
+            ).unwrap();
+
+            if self.labels {
+                let (x, y) = centre(&cells);
+                writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="{}" font-family="sans-serif" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                    x * size + size / 2,
+                    y * size + size / 2,
+                    size * 3 / 5,
+                    escape(label),
+                ).unwrap();
+            }
+        }
+    }
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        SvgRenderer::new()
+    }
+}
+
+impl Renderer<Solution<(i8, i8)>> for SvgRenderer {
+    fn render(&self, solution: &Solution<(i8, i8)>) -> String {
+        let bounds = Bounds::of(&solution.pieces());
+        let mut body = String::new();
+        self.pieces(&mut body, &solution.pieces(), (-bounds.min.0, -bounds.min.1));
+
+        self.document(bounds.width(), bounds.height(), &body)
+    }
+}
+
+/// Bounding rectangle of the cells of pieces.
+struct Bounds {
+    min: (i32, i32),
+    max: (i32, i32),
+}
+
+impl Bounds {
+    fn of(pieces: &[&Piece<(i8, i8)>]) -> Bounds {
+        let coordinates: Vec<(i32, i32)> = pieces
+            .iter()
+            .flat_map(|piece| piece.iter())
+            .map(|position| {
+                let (x, y) = *position.coordinates();
+                (x as i32, y as i32)
+            })
+            .collect();
+        if coordinates.is_empty() {
+            return Bounds { min: (0, 0), max: (-1, -1) };
+        }
+
+        Bounds {
+            min: (coordinates.iter().map(|c| c.0).min().unwrap(), coordinates.iter().map(|c| c.1).min().unwrap()),
+            max: (coordinates.iter().map(|c| c.0).max().unwrap(), coordinates.iter().map(|c| c.1).max().unwrap()),
+        }
+    }
+
+    fn width(&self) -> i32 {
+        self.max.0 - self.min.0 + 1
+    }
+
+    fn height(&self) -> i32 {
+        self.max.1 - self.min.1 + 1
+    }
+}
+
+/// Determine the closed outlines of a collection of cells.
+///
+/// Each outline is a sequence of corner points, traced clockwise around the
+/// cells and counter clockwise around holes.
+fn outlines(cells: &[(i32, i32)]) -> Vec<Vec<(i32, i32)>> {
+    let mut edges: BTreeMap<(i32, i32), Vec<(i32, i32)>> = BTreeMap::new();
+    for &(x, y) in cells {
+        let sides = [
+            ((x, y - 1), (x, y), (x + 1, y)),
+            ((x + 1, y), (x + 1, y), (x + 1, y + 1)),
+            ((x, y + 1), (x + 1, y + 1), (x, y + 1)),
+            ((x - 1, y), (x, y + 1), (x, y)),
+        ];
+        for &(neighbour, start, end) in sides.iter() {
+            if !cells.contains(&neighbour) {
+                edges.entry(start).or_default().push(end);
+            }
+        }
+    }
+
+    let mut result = vec!();
+    while let Some(&start) = edges.keys().next() {
+        let mut outline = vec!(start);
+        let mut current = start;
+        loop {
+            let ends = edges.get_mut(&current).unwrap();
+            let next = ends.remove(0);
+            if ends.is_empty() {
+                edges.remove(&current);
+            }
+            if next == start {
+                break;
+            }
+            outline.push(next);
+            current = next;
+        }
+        result.push(corners(outline));
+    }
+    result
+}
+
+/// Remove the points of a closed outline that lie on a straight line.
+fn corners(outline: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
+    let n = outline.len();
+    (0..n)
+        .filter(|&index| {
+            let previous = outline[(index + n - 1) % n];
+            let current = outline[index];
+            let next = outline[(index + 1) % n];
+            (current.0 - previous.0, current.1 - previous.1) != (next.0 - current.0, next.1 - current.1)
+        })
+        .map(|index| outline[index])
+        .collect()
+}
+
+/// The cell closest to the centre of mass of the cells.
+fn centre(cells: &[(i32, i32)]) -> (i32, i32) {
+    let n = cells.len() as f64;
+    let cx = cells.iter().map(|c| c.0 as f64 + 0.5).sum::<f64>() / n;
+    let cy = cells.iter().map(|c| c.1 as f64 + 0.5).sum::<f64>() / n;
+    let distance = |c: &(i32, i32)| (c.0 as f64 + 0.5 - cx).powi(2) + (c.1 as f64 + 0.5 - cy).powi(2);
+
+    *cells
+        .iter()
+        .min_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::piece::Position;
+    use super::super::Colour;
+    use super::*;
+
+    fn solution() -> Solution<(i8, i8)> {
+        Solution::empty()
+            .record(&Piece::named(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0), Position::d2(0, 1)), "L"))
+            .record(&Piece::new(vec!(Position::d2(1, 1), Position::d2(2, 1))))
+    }
+
+    #[test]
+    fn outlines_should_trace_corners() {
+        let outline = outlines(&[(0, 0), (1, 0), (2, 0), (0, 1)]);
+
+        assert_eq!(outline, vec!(vec!((0, 0), (3, 0), (3, 1), (1, 1), (1, 2), (0, 2))));
+    }
+
+    #[test]
+    fn outlines_should_include_holes() {
+        let ring = [(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+        assert_eq!(outlines(&ring).len(), 2);
+    }
+
+    #[test]
+    fn should_render_a_path_per_piece() {
+        let svg = SvgRenderer::new().with_cell_size(10).with_labels().render(&solution());
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="30" viewBox="0 0 40 30">"#));
+        assert!(svg.contains(r#"<path d="M0,0L30,0L30,10L10,10L10,20L0,20Z""#));
+        assert!(svg.contains(r#"<path d="M10,10L30,10L30,20L10,20Z""#));
+        assert!(svg.contains(">L</text>"));
+    }
+
+    #[test]
+    fn should_tile_solutions_on_a_sheet() {
+        let solutions = vec!(solution(), solution(), solution());
+
+        let svg = SvgRenderer::new().with_cell_size(10).with_columns(2).render_sheet(&solutions);
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="80" height="60" viewBox="0 0 80 60">"#));
+        assert_eq!(svg.matches("<path").count(), 6);
+        assert_eq!(svg.matches(&Colour::distinct(0).hex()).count(), 3);
+    }
+
+    #[test]
+    fn pieces_with_the_same_name_should_have_distinct_colours() {
+        let solution = Solution::empty()
+            .record(&Piece::named(vec!(Position::d2(0, 0), Position::d2(1, 0)), "I"))
+            .record(&Piece::named(vec!(Position::d2(0, 1), Position::d2(1, 1)), "I"));
+
+        let svg = SvgRenderer::new().render(&solution);
+
+        assert!(svg.contains(&Colour::distinct(0).hex()));
+        assert!(svg.contains(&Colour::distinct(1).hex()));
+    }
+}
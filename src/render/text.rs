@@ -0,0 +1,359 @@
+//! Rendering solutions and targets as text.
+
+use super::{labels, Axis, Renderer};
+use super::super::puzzle::solver::{Solution, Target};
+
+/// Coordinates of a cell with the index of the label it shows.
+type Cell<C> = (C, usize);
+
+/// Renders a `Solution` or a `Target` as a grid of characters.
+///
+/// Every cell shows the label of the piece that covers it, or `#` for a cell of
+/// a `Target`. Rows are printed top to bottom with increasing `y`, columns left
+/// to right with increasing `x`. Spatial subjects are cut into layers along an
+/// axis, which are printed side by side.
+#[derive(Debug, Clone)]
+pub struct TextRenderer {
+    borders: bool,
+    empty: char,
+    axis: Axis,
+}
+
+impl TextRenderer {
+    /// Create a `TextRenderer` without borders, that marks empty cells with `.`
+    /// and cuts layers along the z-axis.
+    pub fn new() -> TextRenderer {
+        TextRenderer { borders: false, empty: '.', axis: Axis::Z }
+    }
+
+    /// Create a `TextRenderer` from this one that draws borders between different pieces.
+    pub fn with_borders(self) -> TextRenderer {
+        TextRenderer { borders: true, ..self }
+    }
+
+    /// Create a `TextRenderer` from this one that marks empty cells with `empty`.
+    pub fn with_empty(self, empty: char) -> TextRenderer {
+        TextRenderer { empty, ..self }
+    }
+
+    /// Create a `TextRenderer` from this one that cuts layers along `axis`.
+    pub fn with_axis(self, axis: Axis) -> TextRenderer {
+        TextRenderer { axis, ..self }
+    }
+
+    fn planar(&self, cells: &[Cell<(i8, i8)>], labels: &[String]) -> String {
+        match Bounds::of(cells.iter().map(|cell| cell.0)) {
+            Some(bounds) => {
+                let grid = bounds.grid(cells.iter().cloned());
+                self.lines(&grid, labels).join("\n")
+            },
+            None => String::new(),
+        }
+    }
+
+    fn layered(&self, cells: &[Cell<(i8, i8, i8)>], labels: &[String]) -> String {
+        let split: Vec<(i8, Cell<(i8, i8)>)> = cells
+            .iter()
+            .map(|&(coordinates, index)| {
+                let (layer, planar) = self.axis.split(coordinates);
+                (layer, (planar, index))
+            })
+            .collect();
+        let bounds = match Bounds::of(split.iter().map(|cell| (cell.1).0)) {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+        let first = split.iter().map(|cell| cell.0).min().unwrap();
+        let last = split.iter().map(|cell| cell.0).max().unwrap();
+
+        let blocks: Vec<Vec<String>> = (first..=last)
+            .map(|layer| {
+                let grid = bounds.grid(split.iter().filter(|cell| cell.0 == layer).map(|cell| cell.1));
+                let mut block = vec!(format!("{}={}", self.axis.name(), layer));
+                block.extend(self.lines(&grid, labels));
+                block
+            })
+            .collect();
+
+        let height = blocks.iter().map(|block| block.len()).max().unwrap_or(0);
+        (0..height)
+            .map(|row| {
+                let parts: Vec<String> = blocks
+                    .iter()
+                    .map(|block| {
+                        let width = block.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+                        let line = block.get(row).map(String::as_str).unwrap_or("");
+                        format!("{:<width$}", line, width = width)
+                    })
+                    .collect();
+                parts.join("  ").trim_end().to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn lines(&self, grid: &Grid, labels: &[String]) -> Vec<String> {
+        let width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(1).max(1);
+        let cell = |column: usize, row: usize| -> String {
+            match grid.cells[row * grid.width + column] {
+                Some(index) => format!("{:<width$}", labels[index], width = width),
+                None => self.empty.to_string().repeat(width),
+            }
+        };
+
+        let mut lines: Vec<String> = vec!();
+        if self.borders {
+            for row in 0..=grid.height {
+                let mut line = String::new();
+                for column in 0..=grid.width {
+                    line.push(grid.corner(column, row));
+                    if column < grid.width {
+                        let wall = if grid.horizontal_wall(column, row) { '─' } else { ' ' };
+                        line.push_str(&wall.to_string().repeat(width));
+                    }
+                }
+                lines.push(line);
+
+                if row < grid.height {
+                    let mut line = String::new();
+                    for column in 0..=grid.width {
+                        line.push(if grid.vertical_wall(column, row) { '│' } else { ' ' });
+                        if column < grid.width {
+                            line.push_str(&cell(column, row));
+                        }
+                    }
+                    lines.push(line);
+                }
+            }
+        } else {
+            let separator = if width > 1 { " " } else { "" };
+            for row in 0..grid.height {
+                let cells: Vec<String> = (0..grid.width).map(|column| cell(column, row)).collect();
+                lines.push(cells.join(separator));
+            }
+        }
+
+        lines.iter().map(|line| line.trim_end().to_string()).collect()
+    }
+}
+
+impl Default for TextRenderer {
+    fn default() -> Self {
+        TextRenderer::new()
+    }
+}
+
+impl Renderer<Solution<(i8, i8)>> for TextRenderer {
+    fn render(&self, solution: &Solution<(i8, i8)>) -> String {
+        let pieces = solution.pieces();
+        let cells: Vec<Cell<(i8, i8)>> = pieces
+            .iter()
+            .enumerate()
+            .flat_map(|(index, piece)| piece.iter().map(move |position| (*position.coordinates(), index)))
+            .collect();
+
+        self.planar(&cells, &labels(&pieces))
+    }
+}
+
+impl Renderer<Solution<(i8, i8, i8)>> for TextRenderer {
+    fn render(&self, solution: &Solution<(i8, i8, i8)>) -> String {
+        let pieces = solution.pieces();
+        let cells: Vec<Cell<(i8, i8, i8)>> = pieces
+            .iter()
+            .enumerate()
+            .flat_map(|(index, piece)| piece.iter().map(move |position| (*position.coordinates(), index)))
+            .collect();
+
+        self.layered(&cells, &labels(&pieces))
+    }
+}
+
+impl Renderer<Target<(i8, i8)>> for TextRenderer {
+    fn render(&self, target: &Target<(i8, i8)>) -> String {
+        let cells: Vec<Cell<(i8, i8)>> = target.iter().map(|position| (*position.coordinates(), 0)).collect();
+
+        self.planar(&cells, &[String::from("#")])
+    }
+}
+
+impl Renderer<Target<(i8, i8, i8)>> for TextRenderer {
+    fn render(&self, target: &Target<(i8, i8, i8)>) -> String {
+        let cells: Vec<Cell<(i8, i8, i8)>> = target.iter().map(|position| (*position.coordinates(), 0)).collect();
+
+        self.layered(&cells, &[String::from("#")])
+    }
+}
+
+/// Bounding rectangle of planar coordinates.
+struct Bounds {
+    min: (i8, i8),
+    max: (i8, i8),
+}
+
+impl Bounds {
+    fn of<I>(coordinates: I) -> Option<Bounds> where I: Iterator<Item=(i8, i8)> {
+        coordinates.fold(None, |bounds, (x, y)| {
+            match bounds {
+                None => Some(Bounds { min: (x, y), max: (x, y) }),
+                Some(Bounds { min, max }) => Some(Bounds {
+                    min: (min.0.min(x), min.1.min(y)),
+                    max: (max.0.max(x), max.1.max(y)),
+                }),
+            }
+        })
+    }
+
+    fn grid<I>(&self, cells: I) -> Grid where I: Iterator<Item=Cell<(i8, i8)>> {
+        let width = (self.max.0 as isize - self.min.0 as isize + 1) as usize;
+        let height = (self.max.1 as isize - self.min.1 as isize + 1) as usize;
+        let mut grid = Grid::new(width, height);
+        for ((x, y), index) in cells {
+            grid.set((x as isize - self.min.0 as isize) as usize, (y as isize - self.min.1 as isize) as usize, index);
+        }
+        grid
+    }
+}
+
+/// Rectangular grid of cells, each optionally covered by the piece with that index.
+struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<usize>>,
+}
+
+impl Grid {
+    fn new(width: usize, height: usize) -> Grid {
+        Grid { width, height, cells: vec!(None; width * height) }
+    }
+
+    fn set(&mut self, column: usize, row: usize, index: usize) {
+        self.cells[row * self.width + column] = Some(index);
+    }
+
+    fn get(&self, column: isize, row: isize) -> Option<usize> {
+        if 0 <= column && (column as usize) < self.width && 0 <= row && (row as usize) < self.height {
+            self.cells[row as usize * self.width + column as usize]
+        } else {
+            None
+        }
+    }
+
+    /// Is there a wall above the cell at `column` in `row`.
+    fn horizontal_wall(&self, column: usize, row: usize) -> bool {
+        let (column, row) = (column as isize, row as isize);
+        self.get(column, row - 1) != self.get(column, row)
+    }
+
+    /// Is there a wall left of the cell at `column` in `row`.
+    fn vertical_wall(&self, column: usize, row: usize) -> bool {
+        let (column, row) = (column as isize, row as isize);
+        self.get(column - 1, row) != self.get(column, row)
+    }
+
+    /// Box-drawing character where the walls at the top left of the cell at `column` in `row` meet.
+    fn corner(&self, column: usize, row: usize) -> char {
+        let (c, r) = (column as isize, row as isize);
+        let up = self.get(c - 1, r - 1) != self.get(c, r - 1);
+        let down = self.get(c - 1, r) != self.get(c, r);
+        let left = self.get(c - 1, r - 1) != self.get(c - 1, r);
+        let right = self.get(c, r - 1) != self.get(c, r);
+
+        match (up, down, left, right) {
+            (false, false, false, false) => ' ',
+            (true, true, false, false) => '│',
+            (false, false, true, true) => '─',
+            (false, true, false, true) => '┌',
+            (false, true, true, false) => '┐',
+            (true, false, false, true) => '└',
+            (true, false, true, false) => '┘',
+            (true, true, false, true) => '├',
+            (true, true, true, false) => '┤',
+            (false, true, true, true) => '┬',
+            (true, false, true, true) => '┴',
+            (true, true, true, true) => '┼',
+            (true, false, false, false) => '╵',
+            (false, true, false, false) => '╷',
+            (false, false, true, false) => '╴',
+            (false, false, false, true) => '╶',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::piece::{Position, Piece};
+    use super::super::super::util::target::brick;
+    use super::*;
+
+    fn solution() -> Solution<(i8, i8)> {
+        Solution::empty()
+            .record(&Piece::named(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(2, 0), Position::d2(0, 1)), "L"))
+            .record(&Piece::new(vec!(Position::d2(1, 1), Position::d2(2, 1))))
+    }
+
+    #[test]
+    fn should_render_labels_in_a_grid() {
+        let output = TextRenderer::new().render(&solution());
+
+        assert_eq!(output, "LLL\nLAA");
+    }
+
+    #[test]
+    fn should_mark_empty_cells() {
+        let solution = Solution::empty()
+            .record(&Piece::new(vec!(Position::d2(0, 0))))
+            .record(&Piece::new(vec!(Position::d2(1, 1))));
+
+        let output = TextRenderer::new().with_empty(' ').render(&solution);
+
+        assert_eq!(output, "A\n B");
+    }
+
+    #[test]
+    fn should_draw_borders_between_pieces() {
+        let output = TextRenderer::new().with_borders().render(&solution());
+
+        assert_eq!(output, [
+            "┌─────┐",
+            "│L L L│",
+            "│ ┌───┤",
+            "│L│A A│",
+            "└─┴───┘",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn should_render_layers_side_by_side() {
+        let solution = Solution::empty()
+            .record(&Piece::new(vec!(Position::new(0, 0, 0), Position::new(1, 0, 0), Position::new(0, 0, 1))))
+            .record(&Piece::new(vec!(Position::new(1, 0, 1))));
+
+        let output = TextRenderer::new().render(&solution);
+
+        assert_eq!(output, "z=0  z=1\nAA   AB");
+    }
+
+    #[test]
+    fn should_render_layers_along_an_axis() {
+        let solution = Solution::empty()
+            .record(&Piece::new(vec!(Position::new(0, 0, 0), Position::new(1, 0, 0), Position::new(0, 0, 1))))
+            .record(&Piece::new(vec!(Position::new(1, 0, 1))));
+
+        let output = TextRenderer::new().with_axis(Axis::X).render(&solution);
+
+        assert_eq!(output, "x=0  x=1\nA    A\nA    B");
+    }
+
+    #[test]
+    fn should_render_a_target() {
+        let output = TextRenderer::new().with_borders().render(&brick(2, 1, 2));
+
+        assert_eq!(output, [
+            "z=0    z=1",
+            "┌───┐  ┌───┐",
+            "│# #│  │# #│",
+            "└───┘  └───┘",
+        ].join("\n"));
+    }
+}
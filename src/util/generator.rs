@@ -0,0 +1,300 @@
+//! Generating puzzles that have exactly one solution.
+//!
+//! A `Generator` either grows random targets of the volume of its `Bag`, or
+//! picks targets from a given family, and pins pieces in place until the
+//! puzzle has a unique solution. Pieces are pinned from a solution that
+//! differs from an other one, so the puzzle stays solvable while the number
+//! of solutions shrinks.
+//!
+//! ```
+//! use pack::puzzle::piece::{Position, Template};
+//! use pack::puzzle::pieces::Bag;
+//! use pack::util::generator::Generator;
+//! use pack::util::target::rectangle;
+//!
+//! let domino = Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)));
+//! let mut generator = Generator::new(Bag::new(vec!((4, domino))), 2021);
+//!
+//! let challenge = generator.pinned_challenge(&[rectangle(4, 2)]).unwrap();
+//! assert!(challenge.pins() > 0);
+//! ```
+
+use std::collections::BTreeSet;
+
+use super::super::puzzle::difficulty::{difficulty, Difficulty};
+use super::super::puzzle::piece::{CubeSymmetry, MinimumPosition, Normalizable, Piece, Position, Positionable, Transformable, Translatable, Translation};
+use super::super::puzzle::pieces::{Bag, UNLIMITED};
+use super::super::puzzle::setup::Setup;
+use super::super::puzzle::solver::{Placement, Solution, Target};
+use super::super::puzzle::uniqueness::{uniqueness_of, Uniqueness};
+use super::super::puzzle::{Neighbours, VectorAdd, VectorDifference};
+use super::random::Random;
+
+/// A puzzle with a unique solution.
+#[derive(Debug, Clone)]
+pub struct Challenge<T> {
+    target: Target<T>,
+    setup: Setup<T>,
+    solution: Solution<T>,
+}
+
+impl<T> Challenge<T> where T: Clone {
+    /// The `Target` to pack, including the positions of the pinned pieces.
+    pub fn target(&self) -> &Target<T> {
+        &self.target
+    }
+
+    /// The `Setup` with the pinned pieces, that has a unique solution.
+    pub fn setup(&self) -> &Setup<T> {
+        &self.setup
+    }
+
+    /// The unique solution, including the pinned pieces.
+    pub fn solution(&self) -> &Solution<T> {
+        &self.solution
+    }
+
+    /// The number of pinned pieces.
+    pub fn pins(&self) -> usize {
+        self.setup.pinned().len()
+    }
+
+    /// The number of pieces a player needs to place.
+    pub fn free(&self) -> usize {
+        self.solution.len() - self.pins()
+    }
+}
+
+/// Generates `Challenge`s from the pieces in a `Bag`.
+#[derive(Debug, Clone)]
+pub struct Generator<T> {
+    bag: Bag<T>,
+    random: Random,
+    attempts: usize,
+    maximum_pins: usize,
+    minimum_free: usize,
+    difficulty: Option<Difficulty>,
+}
+
+impl<T> Generator<T> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> + Neighbours<T> {
+    /// Create a `Generator` for the pieces in the `Bag`. The same `seed`
+    /// generates the same `Challenge`s.
+    ///
+    /// By default a `Generator` makes 100 attempts, pins as many pieces as
+    /// needed and leaves at least one piece to place.
+    pub fn new(bag: Bag<T>, seed: u64) -> Generator<T> {
+        let maximum_pins = bag.pieces();
+
+        Generator { bag, random: Random::new(seed), attempts: 100, maximum_pins, minimum_free: 1, difficulty: None }
+    }
+
+    /// Give up after `attempts` targets that did not lead to a `Challenge`.
+    pub fn with_attempts(self, attempts: usize) -> Generator<T> {
+        Generator { attempts, ..self }
+    }
+
+    /// Pin at most `maximum_pins` pieces. With zero only targets that have
+    /// a unique solution by themselves make a `Challenge`.
+    pub fn with_maximum_pins(self, maximum_pins: usize) -> Generator<T> {
+        Generator { maximum_pins, ..self }
+    }
+
+    /// Leave at least `minimum_free` pieces for the player to place. More
+    /// free pieces make harder challenges.
+    pub fn with_minimum_free(self, minimum_free: usize) -> Generator<T> {
+        Generator { minimum_free, ..self }
+    }
+
+    /// Only make `Challenge`s that are graded as `difficulty`.
+    pub fn with_difficulty(self, difficulty: Difficulty) -> Generator<T> {
+        Generator { difficulty: Some(difficulty), ..self }
+    }
+
+    /// Generate a `Challenge` on one of the `targets`, starting with a
+    /// randomly pinned piece.
+    pub fn pinned_challenge(&mut self, targets: &[Target<T>]) -> Option<Challenge<T>> {
+        for _ in 0..self.attempts {
+            let target = self.random.choose(targets)?.clone();
+            let mut setup = Setup::new(target.clone(), self.bag.clone());
+            if self.maximum_pins > 0 {
+                let placements = placements(&target, &self.bag);
+                let (template, symmetry, translation) = match self.random.choose(&placements) {
+                    Some(placement) => placement.clone(),
+                    None => continue,
+                };
+                setup = match setup.pin(template, symmetry, translation) {
+                    Ok(setup) => setup,
+                    Err(_) => continue,
+                };
+            }
+            if let Some(challenge) = self.complete(target, setup) {
+                return Some(challenge);
+            }
+        }
+        None
+    }
+
+    /// Generate a `Challenge` on a randomly grown target of the volume of
+    /// the `Bag`. A `Bag` with an `UNLIMITED` volume has no such target.
+    pub fn shaped_challenge(&mut self) -> Option<Challenge<T>> {
+        let start = self.bag.iter().find_map(|(_, template)| template.iter().next())?;
+        let volume = self.bag.volume();
+        if volume == UNLIMITED {
+            return None;
+        }
+        for _ in 0..self.attempts {
+            let target = self.grow(&start, volume);
+            let setup = Setup::new(target.clone(), self.bag.clone());
+            if let Some(challenge) = self.complete(target, setup) {
+                return Some(challenge);
+            }
+        }
+        None
+    }
+
+    /// Pin pieces of a solution that differ from an other solution, until the
+    /// solution is unique.
+    fn complete(&mut self, target: Target<T>, setup: Setup<T>) -> Option<Challenge<T>> {
+        let mut setup = setup;
+        loop {
+            match uniqueness_of(&setup).ok()? {
+                Uniqueness::Unsolvable => return None,
+                Uniqueness::Unique(solution) => {
+                    let challenge = Challenge { target, setup, solution };
+                    if challenge.free() < self.minimum_free {
+                        return None;
+                    }
+                    if let Some(wanted) = self.difficulty {
+                        if difficulty(&challenge.setup).ok()?.grade() != wanted {
+                            return None;
+                        }
+                    }
+                    return Some(challenge);
+                },
+                Uniqueness::Multiple(solution, other) => {
+                    if setup.pinned().len() >= self.maximum_pins {
+                        return None;
+                    }
+                    let differences: Vec<&Placement<T>> = solution.placements()
+                        .iter()
+                        .filter(|placement| !other.pieces().iter().any(|piece| same_cells(piece, placement.piece())))
+                        .collect();
+                    let placement = *self.random.choose(&differences)?;
+                    setup = setup.pin_placement(placement).ok()?;
+                },
+            }
+        }
+    }
+
+    /// Grow a random connected shape of `volume` cells from `start`.
+    fn grow(&mut self, start: &Position<T>, volume: usize) -> Target<T> {
+        let mut cells: BTreeSet<T> = BTreeSet::new();
+        let mut frontier: Vec<T> = vec!(start.coordinates().clone());
+        while cells.len() < volume && !frontier.is_empty() {
+            let cell = frontier.swap_remove(self.random.below(frontier.len()));
+            if cells.insert(cell.clone()) {
+                frontier.extend(cell.neighbours().into_iter().filter(|neighbour| !cells.contains(neighbour)));
+            }
+        }
+
+        Target::new(cells.into_iter().map(Position::from).collect())
+    }
+}
+
+/// Every way to place a piece of the `Bag` in the `Target`, as the index of
+/// the `Template`, the symmetry and the translation.
+fn placements<T>(target: &Target<T>, bag: &Bag<T>) -> Vec<(usize, CubeSymmetry, Translation<T>)> where T: Clone + PartialOrd + Ord + Transformable + Normalizable<T> + VectorDifference<T> + VectorAdd<T> {
+    let mut result = vec!();
+    for (index, (_, template)) in bag.iter().enumerate() {
+        for (symmetry, orientation) in template.orientations() {
+            let mut oriented = Piece::from(template.clone());
+            oriented.transform(&symmetry);
+            let anchor = oriented.minimum_position().unwrap();
+            let reference = orientation.minimum_position().unwrap();
+            for cell in target.iter() {
+                let mut piece = orientation.clone();
+                piece.translate(&reference.to(&cell));
+                if target.fits(&piece) {
+                    result.push((index, symmetry.clone(), anchor.to(&cell)));
+                }
+            }
+        }
+    }
+    result
+}
+
+fn same_cells<T>(piece: &Piece<T>, other: &Piece<T>) -> bool where T: Clone + Ord {
+    piece.iter().eq(other.iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::piece::Template;
+    use super::super::super::puzzle::uniqueness::uniqueness;
+    use super::super::super::puzzle::verification::verify;
+    use super::super::target::rectangle;
+    use super::*;
+
+    fn bag() -> Bag<(i8, i8)> {
+        Bag::new(vec!(
+            (2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(1, 1)))),
+            (2, Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))),
+        ))
+    }
+
+    #[test]
+    fn pinned_challenges_should_have_a_unique_solution() {
+        let challenge = Generator::new(bag(), 3).pinned_challenge(&[rectangle(5, 2), rectangle(2, 5)]).unwrap();
+
+        assert!(challenge.pins() > 0);
+        assert!(challenge.free() >= 1);
+        assert!(uniqueness_of(challenge.setup()).unwrap().is_unique());
+        assert_eq!(verify(challenge.target(), &bag(), challenge.solution()), Ok(()));
+    }
+
+    #[test]
+    fn pinned_challenges_should_survive_targets_without_placements() {
+        let targets = [rectangle(1, 1), rectangle(5, 2)];
+
+        assert!((0..8).all(|seed| Generator::new(bag(), seed).pinned_challenge(&targets).is_some()));
+    }
+
+    #[test]
+    fn shaped_challenges_should_have_a_unique_solution() {
+        let challenge = Generator::new(bag(), 5).with_maximum_pins(0).with_minimum_free(4).shaped_challenge().unwrap();
+
+        assert_eq!(challenge.pins(), 0);
+        assert_eq!(challenge.target().iter().count(), 10);
+        assert!(uniqueness(challenge.target(), &bag()).unwrap().is_unique());
+    }
+
+    #[test]
+    fn challenges_should_be_reproducible() {
+        let first = Generator::new(bag(), 11).shaped_challenge().unwrap();
+        let second = Generator::new(bag(), 11).shaped_challenge().unwrap();
+
+        assert_eq!(first.target(), second.target());
+        assert_eq!(first.solution().pieces(), second.solution().pieces());
+    }
+
+    #[test]
+    fn challenges_should_have_the_requested_difficulty() {
+        let challenge = Generator::new(bag(), 7).with_difficulty(Difficulty::Easy).shaped_challenge().unwrap();
+
+        assert_eq!(difficulty(challenge.setup()).unwrap().grade(), Difficulty::Easy);
+    }
+
+    #[test]
+    fn unlimited_bags_should_not_shape_challenges() {
+        let bag = Bag::unlimited(vec!(Template::new(vec!(Position::d2(0, 0), Position::d2(1, 0)))));
+
+        assert!(Generator::new(bag, 1).shaped_challenge().is_none());
+    }
+
+    #[test]
+    fn impossible_constraints_should_give_up() {
+        let mut generator = Generator::new(bag(), 1).with_attempts(5).with_minimum_free(5);
+
+        assert!(generator.pinned_challenge(&[rectangle(5, 2)]).is_none());
+    }
+}
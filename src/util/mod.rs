@@ -1,3 +1,6 @@
 //! Various utilities for easily describing packing problems.
 
+pub mod generator;
+pub mod polyform;
+pub mod random;
 pub mod target;
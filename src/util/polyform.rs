@@ -0,0 +1,205 @@
+//! Enumerating polyominoes and polycubes.
+//!
+//! Shapes are generated with Redelmeier's algorithm, which grows every fixed
+//! shape exactly once from its first cell. Shapes that are equivalent under
+//! the requested `Equivalence` are reduced to a single canonical `Template`.
+
+use std::collections::{BTreeSet, HashSet};
+
+use super::super::puzzle::piece::{CubeSymmetry, CubeSymmetryIterator, Position, Template, Transformable};
+
+type Cell = (i8, i8, i8);
+
+/// The largest size of a shape. Larger shapes would leave the range of the coordinates.
+pub const MAX_SIZE: usize = i8::MAX as usize + 1;
+
+/// When two shapes are considered the same.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Equivalence {
+    /// Shapes are the same when they differ by a translation.
+    Fixed,
+    /// Shapes are the same when they differ by a rotation.
+    OneSided,
+    /// Shapes are the same when they differ by a rotation or a reflection.
+    Free,
+}
+
+/// Enumerate the polyominoes with `size` squares.
+///
+/// The `Template`s are the least of the equivalent shapes with their minimum
+/// position at the origin, and are sorted. Free polyominoes are in the
+/// `canonical` form of `Template`.
+///
+/// # Panics
+///
+/// When `size` exceeds `MAX_SIZE`.
+pub fn polyominoes(size: usize, equivalence: Equivalence) -> Vec<Template<(i8, i8)>> {
+    let symmetries: Vec<CubeSymmetry> = match equivalence {
+        Equivalence::Fixed => vec!(CubeSymmetry::E0123),
+        Equivalence::OneSided => CubeSymmetryIterator::new().filter(<(i8, i8)>::admits).filter(keeps_the_plane_sides).collect(),
+        Equivalence::Free => CubeSymmetryIterator::new().filter(<(i8, i8)>::admits).collect(),
+    };
+
+    enumerate(size, 2, &symmetries, false)
+        .into_iter()
+        .map(|cells| Template::new(cells.into_iter().map(|(x, y, _)| Position::d2(x, y)).collect()))
+        .map(|template| if equivalence == Equivalence::Free { template.canonical() } else { template })
+        .collect()
+}
+
+/// Enumerate the polycubes with `size` cubes.
+///
+/// The `Template`s are the least of the equivalent shapes with their minimum
+/// position at the origin, and are sorted. One-sided and free polycubes are
+/// in the `canonical` form of `Template`.
+///
+/// # Panics
+///
+/// When `size` exceeds `MAX_SIZE`.
+pub fn polycubes(size: usize, equivalence: Equivalence) -> Vec<Template<(i8, i8, i8)>> {
+    let symmetries: Vec<CubeSymmetry> = match equivalence {
+        Equivalence::Fixed => vec!(CubeSymmetry::E0123),
+        Equivalence::OneSided | Equivalence::Free => CubeSymmetryIterator::new().filter(<(i8, i8, i8)>::admits).collect(),
+    };
+
+    enumerate(size, 3, &symmetries, equivalence == Equivalence::Free)
+        .into_iter()
+        .map(|cells| Template::new(cells.into_iter().map(|(x, y, z)| Position::new(x, y, z)).collect()))
+        .map(|template| if equivalence == Equivalence::Fixed { template } else { template.canonical() })
+        .collect()
+}
+
+/// Determine if the `symmetry` keeps the sides of the plane in place, i.e.
+/// rotates planar shapes rather than reflects them.
+fn keeps_the_plane_sides(symmetry: &CubeSymmetry) -> bool {
+    let mut normal: Cell = (0, 0, 1);
+    normal.transform(symmetry);
+    normal == (0, 0, 1)
+}
+
+/// Enumerate the canonical shapes of `size` cells in the first `dimension`
+/// axes. Shapes are equivalent when a symmetry, and when `mirror` is set
+/// possibly a reflection, maps one onto the other.
+fn enumerate(size: usize, dimension: usize, symmetries: &[CubeSymmetry], mirror: bool) -> Vec<Vec<Cell>> {
+    assert!(size <= MAX_SIZE, "size {} exceeds the largest size {}", size, MAX_SIZE);
+    let mut fixed = vec!();
+    if size > 0 {
+        let origin = (0, 0, 0);
+        let mut reached: HashSet<Cell> = vec!(origin).into_iter().collect();
+        redelmeier(&mut vec!(origin), &mut vec!(), &mut reached, size, dimension, &mut fixed);
+    }
+
+    let reflections: &[bool] = if mirror { &[false, true] } else { &[false] };
+    fixed
+        .into_iter()
+        .map(|cells| {
+            let mut orientations = vec!();
+            for symmetry in symmetries {
+                for &reflect in reflections {
+                    orientations.push(normalize(cells.iter().map(|&(x, y, z)| {
+                        let mut cell = if reflect { (-x, y, z) } else { (x, y, z) };
+                        cell.transform(symmetry);
+                        cell
+                    }).collect()));
+                }
+            }
+            orientations.into_iter().min().unwrap()
+        })
+        .collect::<BTreeSet<Vec<Cell>>>()
+        .into_iter()
+        .collect()
+}
+
+/// Grow every fixed shape that has the origin as its first cell.
+///
+/// Cells before the origin, in the order of `z`, `y` and then `x`, are never
+/// used. Every cell that is `reached` is tried at most once in a branch, which
+/// makes sure that no shape is generated twice. A shape of at most `MAX_SIZE`
+/// cells stays within `MAX_SIZE - 1` of the origin, so its cells have
+/// neighbours in the range of the coordinates.
+fn redelmeier(untried: &mut Vec<Cell>, shape: &mut Vec<Cell>, reached: &mut HashSet<Cell>, size: usize, dimension: usize, shapes: &mut Vec<Vec<Cell>>) {
+    while let Some(cell) = untried.pop() {
+        shape.push(cell);
+        if shape.len() == size {
+            shapes.push(shape.clone());
+        } else {
+            let fresh: Vec<Cell> = neighbours(cell, dimension)
+                .into_iter()
+                .filter(|&neighbour| (neighbour.2, neighbour.1, neighbour.0) > (0, 0, 0) && !reached.contains(&neighbour))
+                .collect();
+            reached.extend(fresh.iter().cloned());
+            let mut next = untried.clone();
+            next.extend(fresh.iter().cloned());
+            redelmeier(&mut next, shape, reached, size, dimension, shapes);
+            for neighbour in fresh {
+                reached.remove(&neighbour);
+            }
+        }
+        shape.pop();
+    }
+}
+
+fn neighbours((x, y, z): Cell, dimension: usize) -> Vec<Cell> {
+    let mut result = vec!((x + 1, y, z), (x - 1, y, z), (x, y + 1, z), (x, y - 1, z));
+    if dimension > 2 {
+        result.push((x, y, z + 1));
+        result.push((x, y, z - 1));
+    }
+    result
+}
+
+/// Sort the cells and translate them so that the minimum cell is the origin.
+fn normalize(mut cells: Vec<Cell>) -> Vec<Cell> {
+    cells.sort();
+    let (dx, dy, dz) = cells[0];
+    cells.iter().map(|&(x, y, z)| (x - dx, y - dy, z - dz)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts<T, F>(enumeration: F, sizes: usize) -> Vec<usize> where F: Fn(usize) -> Vec<Template<T>> {
+        (1..=sizes).map(|size| enumeration(size).len()).collect()
+    }
+
+    #[test]
+    fn should_count_polyominoes() {
+        assert_eq!(counts(|n| polyominoes(n, Equivalence::Fixed), 6), vec!(1, 2, 6, 19, 63, 216));
+        assert_eq!(counts(|n| polyominoes(n, Equivalence::OneSided), 6), vec!(1, 1, 2, 7, 18, 60));
+        assert_eq!(counts(|n| polyominoes(n, Equivalence::Free), 6), vec!(1, 1, 2, 5, 12, 35));
+    }
+
+    #[test]
+    fn should_count_polycubes() {
+        assert_eq!(counts(|n| polycubes(n, Equivalence::Fixed), 5), vec!(1, 3, 15, 86, 534));
+        assert_eq!(counts(|n| polycubes(n, Equivalence::OneSided), 5), vec!(1, 1, 2, 8, 29));
+        assert_eq!(counts(|n| polycubes(n, Equivalence::Free), 5), vec!(1, 1, 2, 7, 23));
+    }
+
+    #[test]
+    fn templates_should_be_canonical() {
+        let dominoes = polyominoes(2, Equivalence::Free);
+
+        assert_eq!(dominoes, vec!(Template::new(vec!(Position::d2(0, 0), Position::d2(0, 1)))));
+        assert!(polyominoes(0, Equivalence::Free).is_empty());
+    }
+
+    #[test]
+    fn templates_should_agree_with_their_canonical_form() {
+        for template in polyominoes(5, Equivalence::Free) {
+            assert_eq!(template.canonical(), template);
+        }
+        for equivalence in [Equivalence::OneSided, Equivalence::Free].iter() {
+            for template in polycubes(4, *equivalence) {
+                assert_eq!(template.canonical(), template);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the largest size")]
+    fn sizes_beyond_the_coordinates_should_be_refused() {
+        polyominoes(MAX_SIZE + 1, Equivalence::Fixed);
+    }
+}
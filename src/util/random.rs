@@ -0,0 +1,67 @@
+//! A small seedable pseudo random number generator.
+//!
+//! Generated puzzles need to be reproducible from a seed. The generator
+//! implements [SplitMix64](https://prng.di.unimi.it/splitmix64.c), which is
+//! fast and good enough for picking shapes and placements. It is not suitable
+//! for cryptographic use.
+
+/// Seedable source of pseudo random numbers.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Create a `Random` that produces the sequence determined by `seed`.
+    pub fn new(seed: u64) -> Random {
+        Random { state: seed }
+    }
+
+    /// The next pseudo random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A pseudo random number less than `bound`, which should be positive.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// A pseudo randomly chosen element of `items`, or `None` when there are none.
+    pub fn choose<'a, S>(&mut self, items: &'a [S]) -> Option<&'a S> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.below(items.len())])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_reproducible_from_a_seed() {
+        let mut random = Random::new(0);
+        let first: Vec<u64> = (0..3).map(|_| random.next_u64()).collect();
+        let mut again = Random::new(0);
+
+        assert_eq!(first[0], 0xe220_a839_7b1d_cdaf);
+        assert_eq!(first, (0..3).map(|_| again.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(first[0], Random::new(1).next_u64());
+    }
+
+    #[test]
+    fn should_stay_below_the_bound() {
+        let mut random = Random::new(7);
+
+        assert!((0..100).all(|_| random.below(6) < 6));
+        assert_eq!(random.choose::<u8>(&[]), None);
+        assert_eq!(random.choose(&[3]), Some(&3));
+    }
+}
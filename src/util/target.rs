@@ -1,32 +1,120 @@
 //! Utilities for creating `Target`s.
 
+use std::convert::TryFrom;
+
 use super::super::puzzle::solver::Target;
 use super::super::puzzle::piece::Position;
 
 /// Create a brick `Target`, defined by the dimensions.
+///
+/// # Panics
+/// When a dimension exceeds the number of coordinates from 0 to `i8::MAX`,
+/// because the positions would not fit in the coordinates. The same holds for
+/// the other builders of this module.
 pub fn brick(width: u8, height: u8, depth: u8) -> Target<(i8, i8, i8)> {
+    brick_where(width, height, depth, |_, _, _| true)
+}
+
+/// Create a rectangle `Target`, defined by the dimensions.
+pub fn rectangle(width: u8, height: u8) -> Target<(i8, i8)> {
+    rectangle_where(width, height, |_, _| true)
+}
+
+/// Convert an index along an axis into a coordinate.
+fn coordinate<N>(index: N) -> i8 where i8: TryFrom<N> {
+    i8::try_from(index).unwrap_or_else(|_| panic!("index to fit in a coordinate"))
+}
+
+/// Create a `Target` of the positions in a brick, defined by the dimensions,
+/// for which the `predicate` holds.
+pub fn brick_where<F>(width: u8, height: u8, depth: u8, predicate: F) -> Target<(i8, i8, i8)> where F: Fn(i8, i8, i8) -> bool {
     let mut positions: Vec<Position<(i8, i8, i8)>> = vec!();
-    for x in 0..width {
-        for y in 0..height {
-            for z in 0..depth {
-                positions.push(Position::new(x as i8, y as i8, z as i8));
+    for x in (0..width).map(coordinate) {
+        for y in (0..height).map(coordinate) {
+            for z in (0..depth).map(coordinate) {
+                if predicate(x, y, z) {
+                    positions.push(Position::new(x, y, z));
+                }
             }
         }
     }
     Target::new(positions)
 }
 
-/// Create a rectangle `Target`, defined by the dimensions.
-pub fn rectangle(width: u8, height: u8) -> Target<(i8, i8)> {
+/// Create a `Target` of the positions in a rectangle, defined by the
+/// dimensions, for which the `predicate` holds.
+pub fn rectangle_where<F>(width: u8, height: u8, predicate: F) -> Target<(i8, i8)> where F: Fn(i8, i8) -> bool {
+    let mut positions: Vec<Position<(i8, i8)>> = vec!();
+    for x in (0..width).map(coordinate) {
+        for y in (0..height).map(coordinate) {
+            if predicate(x, y) {
+                positions.push(Position::d2(x, y));
+            }
+        }
+    }
+    Target::new(positions)
+}
+
+/// Create a planar `Target` from ASCII art.
+///
+/// Every line is a row, with `y` increasing downwards, and every character a
+/// column. Spaces and `.` are empty, all other characters are part of the
+/// `Target`.
+pub fn from_art(art: &str) -> Target<(i8, i8)> {
     let mut positions: Vec<Position<(i8, i8)>> = vec!();
-    for x in 0..width {
-        for y in 0..height {
-            positions.push(Position::d2(x as i8, y as i8));
+    for (y, line) in art.lines().enumerate() {
+        for (x, character) in line.chars().enumerate() {
+            if character != ' ' && character != '.' {
+                positions.push(Position::d2(coordinate(x), coordinate(y)));
+            }
         }
     }
     Target::new(positions)
 }
 
+/// Create a spatial `Target` from layers of ASCII art, as in `from_art`.
+///
+/// The n-th layer has `z` equal to n.
+pub fn from_layers(layers: &[&str]) -> Target<(i8, i8, i8)> {
+    let mut positions: Vec<Position<(i8, i8, i8)>> = vec!();
+    for (z, layer) in layers.iter().enumerate() {
+        for position in from_art(layer).iter() {
+            let (x, y) = *position.coordinates();
+            positions.push(Position::new(x, y, coordinate(z)));
+        }
+    }
+    Target::new(positions)
+}
+
+/// Create a staircase `Target` with `steps` rows, the n-th row of which has n squares.
+pub fn staircase(steps: u8) -> Target<(i8, i8)> {
+    rectangle_where(steps, steps, |x, y| x <= y)
+}
+
+/// Create a pyramid `Target` with a square base of `base` by `base` cubes.
+///
+/// Every layer is centred on the one below it and is two cubes narrower.
+pub fn pyramid(base: u8) -> Target<(i8, i8, i8)> {
+    let height = base / 2 + base % 2;
+    let side = i16::from(base);
+    brick_where(base, base, height, |x, y, z| {
+        let (x, y, z) = (i16::from(x), i16::from(y), i16::from(z));
+        z <= x && x < side - z && z <= y && y < side - z
+    })
+}
+
+/// Create an L-shaped `Target` that fits in a rectangle, defined by the
+/// dimensions, with legs `thickness` squares wide along the axes.
+pub fn l_shape(width: u8, height: u8, thickness: u8) -> Target<(i8, i8)> {
+    let thickness = i16::from(thickness);
+    rectangle_where(width, height, |x, y| i16::from(x) < thickness || i16::from(y) < thickness)
+}
+
+/// Create a brick `Target`, defined by the dimensions, without the `holes`.
+pub fn brick_with_holes(width: u8, height: u8, depth: u8, holes: &[(i8, i8, i8)]) -> Target<(i8, i8, i8)> {
+    brick_where(width, height, depth, |x, y, z| !holes.contains(&(x, y, z)))
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -54,4 +142,50 @@ mod tests {
 		    assert!(target.fits(&Piece::new(vec!(Position::d2(1,0)))));
 		    assert!(target.fits(&Piece::new(vec!(Position::d2(1,1)))));
 	  }
+
+    #[test]
+    fn should_create_a_target_from_art() {
+        let target = from_art("##.\n.#\n");
+
+        assert_eq!(target.iter().count(), 3);
+        assert!(target.fits(&Piece::new(vec!(Position::d2(0, 0), Position::d2(1, 0), Position::d2(1, 1)))));
+        assert!(!target.fits(&Piece::new(vec!(Position::d2(0, 1)))));
+    }
+
+    #[test]
+    fn should_create_a_target_from_layers() {
+        let target = from_layers(&["##", "#"]);
+
+        assert_eq!(target.iter().count(), 3);
+        assert!(target.fits(&Piece::new(vec!(Position::new(0, 0, 1)))));
+        assert!(!target.fits(&Piece::new(vec!(Position::new(1, 0, 1)))));
+    }
+
+    #[test]
+    fn shapes_should_have_the_expected_volume() {
+        assert_eq!(staircase(4).iter().count(), 10);
+        assert_eq!(pyramid(5).iter().count(), 25 + 9 + 1);
+        assert_eq!(pyramid(4).iter().count(), 16 + 4);
+        assert_eq!(l_shape(4, 5, 2).iter().count(), 4 * 5 - 2 * 3);
+        assert_eq!(brick_with_holes(3, 3, 3, &[(1, 1, 1)]).iter().count(), 26);
+    }
+
+    #[test]
+    fn dimensions_should_reach_the_largest_coordinate() {
+        assert_eq!(rectangle(128, 1).iter().count(), 128);
+        assert_eq!(pyramid(128).iter().count(), (0..64).map(|z| (128 - 2 * z) * (128 - 2 * z)).sum::<usize>());
+        assert_eq!(from_art(&"#".repeat(128)).iter().count(), 128);
+    }
+
+    #[test]
+    #[should_panic]
+    fn dimensions_beyond_the_coordinates_should_be_refused() {
+        rectangle(200, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn art_beyond_the_coordinates_should_be_refused() {
+        from_art(&"#".repeat(129));
+    }
 }
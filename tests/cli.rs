@@ -0,0 +1,128 @@
+//! Runs the `pack` binary on the puzzles in `tests/fixtures`.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn pack(arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_pack")).args(arguments).output().expect("pack to run")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).expect("output to be UTF-8")
+}
+
+#[test]
+fn solve_should_print_every_solution_as_text() {
+    let output = pack(&["solve", &fixture("square.json")]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "I1 I2\nI1 I2\n\nI1 I1\nI2 I2\n\n");
+}
+
+#[test]
+fn solve_should_print_a_solution_per_json_line() {
+    let output = pack(&["solve", &fixture("square.json"), "--format", "json"]);
+
+    assert!(output.status.success());
+    let lines: Vec<String> = stdout(&output).lines().map(String::from).collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines.iter().all(|line| line.starts_with('{') && line.contains(r#""template":0"#)));
+}
+
+#[test]
+fn solve_should_print_a_single_svg_document() {
+    for puzzle in &["square.json", "cube.json"] {
+        let output = pack(&["solve", &fixture(puzzle), "--format", "svg"]);
+
+        assert!(output.status.success());
+        let svg = stdout(&output);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<svg").count(), 1);
+        assert_eq!(svg.matches("</svg>").count(), 1);
+    }
+}
+
+#[test]
+fn count_should_respect_the_limit() {
+    assert_eq!(stdout(&pack(&["count", &fixture("square.json")])), "2\n");
+    assert_eq!(stdout(&pack(&["count", &fixture("square.json"), "--limit", "1"])), "1\n");
+}
+
+#[test]
+fn first_should_print_one_solution() {
+    let output = pack(&["first", &fixture("cube.json"), "--borders"]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("z=0\n┌───┐\n"));
+    assert_eq!(stdout(&pack(&["first", &fixture("cube.json"), "--format", "json"])).lines().count(), 1);
+}
+
+#[test]
+fn verify_should_accept_solutions_of_the_solver() {
+    let solution = stdout(&pack(&["first", &fixture("square.json"), "--format", "json"]));
+    let path: PathBuf = env::temp_dir().join(format!("pack-cli-{}.json", std::process::id()));
+    fs::write(&path, solution).expect("solution to be written");
+
+    let output = pack(&["verify", &fixture("square.json"), path.to_str().unwrap()]);
+    fs::remove_file(&path).ok();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "valid\n");
+}
+
+#[test]
+fn verify_should_reject_overlapping_pieces() {
+    let output = pack(&["verify", &fixture("square.json"), &fixture("overlap.json")]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "invalid: cell (1, 0) is covered more than once\n");
+}
+
+#[test]
+fn verify_should_reject_placements_of_unknown_templates() {
+    let output = pack(&["verify", &fixture("square.json"), &fixture("misplaced.json")]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "invalid: placement 0 does not produce its piece\n");
+}
+
+#[test]
+fn info_should_describe_the_puzzle() {
+    let output = pack(&["info", &fixture("square.json")]);
+
+    assert!(output.status.success());
+    let info = stdout(&output);
+    assert!(info.starts_with("name: two by two\ntarget volume: 4\n"));
+    assert!(info.contains("bag volume: 4 (1 templates, 2 pieces)\n"));
+    assert!(info.ends_with("    0  I                  2      2            2          4\n"));
+}
+
+#[test]
+fn info_should_merge_congruent_templates() {
+    let output = pack(&["info", &fixture("congruent.json")]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("bag volume: 4 (1 templates, 2 pieces)\n"));
+}
+
+#[test]
+fn info_should_refuse_invalid_puzzles() {
+    let output = pack(&["info", &fixture("empty_template.json")]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid puzzle: template 0: no positions"));
+}
+
+#[test]
+fn unknown_commands_should_fail() {
+    let output = pack(&["shuffle", &fixture("square.json")]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown command `shuffle`"));
+}